@@ -0,0 +1,14 @@
+use crate::config::{readable_field, writable_field};
+use crate::{collision, getters, setters};
+use proc_macro2::TokenStream;
+use syn::DeriveInput;
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let mut expanded = getters::expand(input.clone(), readable_field)?;
+
+    expanded.extend(setters::expand(input, writable_field)?);
+
+    collision::check(&expanded)?;
+
+    Ok(expanded)
+}
@@ -0,0 +1,101 @@
+use crate::config::{cfg_attrs, named_fields};
+use crate::ty::phantom;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Ident};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let vis = input.vis;
+
+    let builder: Ident = syn::parse_str(format!("{}Builder", name).as_str()).unwrap();
+
+    let mut fields = vec![];
+    let mut nones = vec![];
+    let mut methods = vec![];
+    let mut required = vec![];
+    let mut defaulted = vec![];
+    let mut values = vec![];
+
+    for (field, config) in named_fields(input.data)? {
+        let ident = field.ident.as_ref().cloned().unwrap();
+        let ty = &field.ty;
+        let cfg = cfg_attrs(&field.attrs);
+
+        if config.skip {
+            values.push(quote! { #(#cfg)* #ident: ::core::default::Default::default() });
+            continue;
+        }
+
+        fields.push(quote! { #(#cfg)* #ident: ::core::option::Option<#ty> });
+        nones.push(quote! { #(#cfg)* #ident: ::core::option::Option::None });
+        methods.push(quote! {
+            #(#cfg)*
+            pub fn #ident(mut self, v: #ty) -> Self {
+                self.#ident = ::core::option::Option::Some(v);
+                self
+            }
+        });
+        values.push(quote! { #(#cfg)* #ident });
+
+        if config.default {
+            let default = config.default_expr(&field.ty)?;
+
+            defaulted.push(quote! {
+                #(#cfg)*
+                let #ident = match self.#ident {
+                    ::core::option::Option::Some(v) => v,
+                    ::core::option::Option::None => #default,
+                };
+            });
+        } else {
+            let missing = format!("{} is not set", ident);
+
+            required.push(quote! {
+                #(#cfg)*
+                let #ident = self.#ident.ok_or(#missing)?;
+            });
+        }
+    }
+
+    // A type parameter used only by a skipped attribute must still be used by the builder
+    if let Some(phantom) = phantom(generics) {
+        fields.push(quote! { __marker: #phantom });
+        nones.push(quote! { __marker: ::core::marker::PhantomData });
+    }
+
+    Ok(quote! {
+        #vis struct #builder #generics #where_clause {
+            #(#fields,)*
+        }
+
+        impl #impl_generics ::core::default::Default for #builder #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#nones,)*
+                }
+            }
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(#methods)*
+
+            pub fn build(self) -> ::core::result::Result<#name #ty_generics, &'static str> {
+                #(#required)*
+                #(#defaulted)*
+
+                ::core::result::Result::Ok(#name {
+                    #(#values,)*
+                })
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn builder() -> #builder #ty_generics {
+                <#builder #ty_generics as ::core::default::Default>::default()
+            }
+        }
+    })
+}
@@ -0,0 +1,59 @@
+use proc_macro2::Span;
+use syn::{Error, Ident, LitStr};
+
+/// Case convention of `#[unprolix(rename_all = "...")]`
+pub(crate) enum Case {
+    Camel,
+    Pascal,
+    ScreamingSnake,
+    Snake,
+}
+
+impl Case {
+    pub fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "camelCase" => Ok(Case::Camel),
+            "PascalCase" => Ok(Case::Pascal),
+            "SCREAMING_SNAKE_CASE" => Ok(Case::ScreamingSnake),
+            "snake_case" => Ok(Case::Snake),
+            _ => Err(Error::new_spanned(
+                lit,
+                "rename_all expects \"camelCase\", \"PascalCase\", \"SCREAMING_SNAKE_CASE\" or \"snake_case\"",
+            )),
+        }
+    }
+
+    /// Convert a snake case name to this convention
+    pub fn apply(&self, name: &str) -> String {
+        let words = name.split('_').filter(|w| !w.is_empty());
+
+        match self {
+            Case::Camel => words
+                .enumerate()
+                .map(|(i, w)| if i == 0 { w.to_string() } else { capitalize(w) })
+                .collect(),
+            Case::Pascal => words.map(capitalize).collect(),
+            Case::ScreamingSnake => name.to_uppercase(),
+            Case::Snake => name.to_string(),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Identifier of a generated method, converted to the case convention if any
+pub(crate) fn method(case: Option<&Case>, name: String) -> Ident {
+    let name = match case {
+        Some(case) => case.apply(&name),
+        None => name,
+    };
+
+    Ident::new(&name, Span::call_site())
+}
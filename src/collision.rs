@@ -0,0 +1,45 @@
+use proc_macro2::{Span, TokenStream};
+use syn::{Error, File, ImplItem, Item};
+
+/// Reject generated code that defines the same method more than once in an impl
+///
+/// Renamed attributes may produce the same method name, which would otherwise be reported as a
+/// confusing duplicate definition. Inherent impls of a single derive share the same namespace.
+pub(crate) fn check(tokens: &TokenStream) -> syn::Result<()> {
+    let file: File = syn::parse2(tokens.clone())?;
+    let mut inherent = vec![];
+
+    for item in file.items {
+        let i = match item {
+            Item::Impl(i) => i,
+            _ => continue,
+        };
+
+        let mut trait_methods = vec![];
+        let methods = if i.trait_.is_some() {
+            &mut trait_methods
+        } else {
+            &mut inherent
+        };
+
+        for item in i.items {
+            if let ImplItem::Method(m) = item {
+                let name = m.sig.ident.to_string();
+
+                if methods.contains(&name) {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        format!(
+                            "the method `{}` is generated more than once; rename the conflicting attributes",
+                            name
+                        ),
+                    ));
+                }
+
+                methods.push(name);
+            }
+        }
+    }
+
+    Ok(())
+}
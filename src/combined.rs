@@ -0,0 +1,27 @@
+use crate::config::{accessor_field, ContainerConfig};
+use crate::{collision, constructor, getters, setters};
+use proc_macro2::TokenStream;
+use syn::DeriveInput;
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let container = ContainerConfig::from_attributes(&input.attrs)?;
+    let all = !(container.constructor || container.getters || container.setters);
+
+    let mut expanded = TokenStream::new();
+
+    if all || container.constructor {
+        expanded.extend(constructor::expand(input.clone())?);
+    }
+
+    if all || container.getters {
+        expanded.extend(getters::expand(input.clone(), accessor_field)?);
+    }
+
+    if all || container.setters {
+        expanded.extend(setters::expand(input, accessor_field)?);
+    }
+
+    collision::check(&expanded)?;
+
+    Ok(expanded)
+}
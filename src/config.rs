@@ -0,0 +1,620 @@
+use crate::case::{method, Case};
+use syn::{
+    parse_quote, Attribute, Data, Error, Expr, Field, Fields, Ident, Index, Lit, LitStr, Member,
+    Meta, MetaNameValue, NestedMeta, Path, Type, Visibility,
+};
+
+/// Keys of the `#[unprolix(...)]` attributes of a field
+#[derive(Default)]
+pub(crate) struct FieldConfig {
+    pub skip: bool,
+    pub skip_reason: Option<LitStr>,
+    pub get: bool,
+    pub set: bool,
+    pub both: bool,
+    pub rename: Option<LitStr>,
+    pub getter_kind: Option<GetterKind>,
+    pub raw: bool,
+    pub as_slice: bool,
+    pub opt_slice: bool,
+    pub elem: Option<LitStr>,
+    pub as_str: bool,
+    pub default: bool,
+    pub default_value: Option<LitStr>,
+    pub overridable: bool,
+    pub arg: Option<LitStr>,
+    pub validate: Option<LitStr>,
+    pub error: Option<LitStr>,
+    pub from_slice: bool,
+    pub collect: bool,
+    pub into: bool,
+    pub uninit: bool,
+    pub dyn_: bool,
+    pub cell_get: bool,
+    pub get_primitive: bool,
+    pub once_get: bool,
+    pub lock: bool,
+    pub as_path: bool,
+    pub as_ref: bool,
+    pub upgrade: bool,
+    pub ok_ref: bool,
+    pub lookup: bool,
+    pub iter_mut: bool,
+    pub with_ref: bool,
+    pub primary: bool,
+    pub display: bool,
+    pub flatten: Vec<(Ident, Option<LitStr>)>,
+    pub deref_inner: bool,
+    pub deref_as: Option<LitStr>,
+    pub modify: bool,
+    pub swap: bool,
+    pub atomic: bool,
+    pub clearable: bool,
+    pub get_or_insert: bool,
+    pub replace_with: bool,
+    pub returns: Option<LitStr>,
+    pub project: Option<LitStr>,
+    pub ty: Option<LitStr>,
+}
+
+impl FieldConfig {
+    pub fn from_field(f: &Field) -> syn::Result<Self> {
+        let mut config = Self {
+            flatten: nested(&f.attrs, "flatten")?,
+            ..Self::default()
+        };
+
+        for (key, value) in keys(&f.attrs)? {
+            match key.to_string().as_str() {
+                "skip" => {
+                    config.skip = true;
+                    config.skip_reason = value;
+                }
+                "get" => config.get = flag(&key, &value)?,
+                "set" => config.set = flag(&key, &value)?,
+                "both" => config.both = flag(&key, &value)?,
+                "rename" => config.rename = Some(required(&key, value)?),
+                "ref" => {
+                    flag(&key, &value)?;
+                    config.getter_kind = Some(GetterKind::Ref);
+                }
+                "clone" => {
+                    flag(&key, &value)?;
+                    config.getter_kind = Some(GetterKind::Clone);
+                }
+                "copy" => {
+                    flag(&key, &value)?;
+                    config.getter_kind = Some(GetterKind::Copy);
+                }
+                "raw" => config.raw = flag(&key, &value)?,
+                "as_slice" => config.as_slice = flag(&key, &value)?,
+                "opt_slice" => config.opt_slice = flag(&key, &value)?,
+                "elem" => config.elem = Some(required(&key, value)?),
+                "as_str" => config.as_str = flag(&key, &value)?,
+                "default" => {
+                    config.default = true;
+                    config.default_value = value;
+                }
+                "overridable" => config.overridable = flag(&key, &value)?,
+                "arg" => config.arg = Some(required(&key, value)?),
+                "validate" => config.validate = Some(required(&key, value)?),
+                "error" => config.error = Some(required(&key, value)?),
+                "from_slice" => config.from_slice = flag(&key, &value)?,
+                "collect" => config.collect = flag(&key, &value)?,
+                "into" => config.into = flag(&key, &value)?,
+                "uninit" => config.uninit = flag(&key, &value)?,
+                "dyn" => config.dyn_ = flag(&key, &value)?,
+                "cell_get" => config.cell_get = flag(&key, &value)?,
+                "get_primitive" => config.get_primitive = flag(&key, &value)?,
+                "once_get" => config.once_get = flag(&key, &value)?,
+                "lock" => config.lock = flag(&key, &value)?,
+                "as_path" => config.as_path = flag(&key, &value)?,
+                "as_ref" => config.as_ref = flag(&key, &value)?,
+                "upgrade" => config.upgrade = flag(&key, &value)?,
+                "ok_ref" => config.ok_ref = flag(&key, &value)?,
+                "lookup" => config.lookup = flag(&key, &value)?,
+                "iter_mut" => config.iter_mut = flag(&key, &value)?,
+                "with_ref" => config.with_ref = flag(&key, &value)?,
+                "primary" => config.primary = flag(&key, &value)?,
+                "display" => config.display = flag(&key, &value)?,
+                "deref_inner" => config.deref_inner = flag(&key, &value)?,
+                "deref_as" => config.deref_as = Some(required(&key, value)?),
+                "modify" => config.modify = flag(&key, &value)?,
+                "swap" => config.swap = flag(&key, &value)?,
+                "atomic" => config.atomic = flag(&key, &value)?,
+                "clearable" => config.clearable = flag(&key, &value)?,
+                "get_or_insert" => config.get_or_insert = flag(&key, &value)?,
+                "replace_with" => config.replace_with = flag(&key, &value)?,
+                "returns" => config.returns = Some(required(&key, value)?),
+                "project" => config.project = Some(required(&key, value)?),
+                "ty" => config.ty = Some(required(&key, value)?),
+                // Read by `nested` and `accessor_attrs`
+                "flatten" | "test_only" => {
+                    flag(&key, &value)?;
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        &key,
+                        format!("unknown attribute key {}", key),
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Name of a generated method, formatted from `rename = "name"` or the attribute name
+    ///
+    /// The positional attributes of tuple structs are named `field_0`, `field_1`, and so on.
+    ///
+    /// The container case convention applies only to attributes that are not renamed.
+    pub fn method(
+        &self,
+        member: &Member,
+        case: Option<&Case>,
+        format: fn(&str) -> String,
+    ) -> Ident {
+        match (&self.rename, member) {
+            (Some(r), _) => method(None, format(&r.value())),
+            (None, Member::Named(i)) => method(case, format(&i.to_string())),
+            (None, Member::Unnamed(i)) => method(case, format(&format!("field_{}", i.index))),
+        }
+    }
+
+    /// Type of the attribute as inspected by the getters, which is `ty = "Type"` if provided, such
+    /// as the definition of a type alias
+    pub fn inspected_ty(&self, field: &Field) -> syn::Result<Type> {
+        match &self.ty {
+            Some(ty) => ty.parse(),
+            None => Ok(field.ty.clone()),
+        }
+    }
+
+    /// Expression of `default = "expr"`, or `<Type as Default>::default()` if no value is
+    /// provided
+    ///
+    /// The call is qualified with the attribute type, so it doesn't depend on inference.
+    pub fn default_expr(&self, ty: &Type) -> syn::Result<Expr> {
+        match &self.default_value {
+            Some(v) => v.parse(),
+            None => Ok(parse_quote!(<#ty as ::core::default::Default>::default())),
+        }
+    }
+}
+
+/// How a getter returns the attribute, set with `ref`, `clone` or `copy`
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum GetterKind {
+    Ref,
+    Clone,
+    Copy,
+}
+
+impl GetterKind {
+    pub fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "ref" => Ok(GetterKind::Ref),
+            "clone" => Ok(GetterKind::Clone),
+            "copy" => Ok(GetterKind::Copy),
+            _ => Err(Error::new_spanned(
+                lit,
+                "getter_kind expects \"ref\", \"clone\" or \"copy\"",
+            )),
+        }
+    }
+}
+
+/// Keys of the `#[unprolix(...)]` attributes of a struct
+#[derive(Default)]
+pub(crate) struct ContainerConfig {
+    pub named_args: bool,
+    pub track_caller: bool,
+    pub inline: bool,
+    pub const_fn: bool,
+    pub const_default: bool,
+    pub derive_default: bool,
+    pub defaults_last: bool,
+    pub singleton: bool,
+    pub partial: bool,
+    pub required_ref: bool,
+    pub into: bool,
+    pub where_clause: bool,
+    pub try_new: bool,
+    pub checked_unchecked: bool,
+    pub async_: bool,
+    pub error: Option<LitStr>,
+    pub gen_error: bool,
+    pub trait_: Option<LitStr>,
+    pub rename_all: Option<Case>,
+    pub getter_kind: Option<GetterKind>,
+    pub copy: bool,
+    pub arrays_as_slices: bool,
+    pub qualified: bool,
+    pub only_type: Option<LitStr>,
+    pub transparent_ref: bool,
+    pub patch: bool,
+    pub reflect: bool,
+    pub document_skipped: bool,
+    pub copy_from: bool,
+    pub constructor: bool,
+    pub getters: bool,
+    pub setters: bool,
+}
+
+impl ContainerConfig {
+    pub fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut config = Self::default();
+
+        for (key, value) in keys(attrs)? {
+            match key.to_string().as_str() {
+                "named_args" => config.named_args = flag(&key, &value)?,
+                "track_caller" => config.track_caller = flag(&key, &value)?,
+                "inline" => config.inline = flag(&key, &value)?,
+                "const_fn" => config.const_fn = flag(&key, &value)?,
+                "const_default" => config.const_default = flag(&key, &value)?,
+                "derive_default" => config.derive_default = flag(&key, &value)?,
+                "defaults_last" => config.defaults_last = flag(&key, &value)?,
+                "singleton" => config.singleton = flag(&key, &value)?,
+                "partial" => config.partial = flag(&key, &value)?,
+                "required_ref" => config.required_ref = flag(&key, &value)?,
+                "into" => config.into = flag(&key, &value)?,
+                "where_clause" => config.where_clause = flag(&key, &value)?,
+                "try_new" => config.try_new = flag(&key, &value)?,
+                "checked_unchecked" => config.checked_unchecked = flag(&key, &value)?,
+                "async" => config.async_ = flag(&key, &value)?,
+                "error" => config.error = Some(required(&key, value)?),
+                "gen_error" => config.gen_error = flag(&key, &value)?,
+                "trait" => config.trait_ = Some(required(&key, value)?),
+                "copy" => config.copy = flag(&key, &value)?,
+                "arrays_as_slices" => config.arrays_as_slices = flag(&key, &value)?,
+                "qualified" => config.qualified = flag(&key, &value)?,
+                "only_type" => config.only_type = Some(required(&key, value)?),
+                "transparent_ref" => config.transparent_ref = flag(&key, &value)?,
+                "patch" => config.patch = flag(&key, &value)?,
+                "reflect" => config.reflect = flag(&key, &value)?,
+                "document_skipped" => config.document_skipped = flag(&key, &value)?,
+                "copy_from" => config.copy_from = flag(&key, &value)?,
+                "constructor" => config.constructor = flag(&key, &value)?,
+                "getters" => config.getters = flag(&key, &value)?,
+                "setters" => config.setters = flag(&key, &value)?,
+                "getter_kind" => {
+                    config.getter_kind = Some(GetterKind::from_lit(&required(&key, value)?)?)
+                }
+                "rename_all" => config.rename_all = Some(Case::from_lit(&required(&key, value)?)?),
+                _ => {
+                    return Err(Error::new_spanned(
+                        &key,
+                        format!("unknown container key {}", key),
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Named fields of a struct with their configuration, in declaration order
+///
+/// Other kinds of input have no fields to generate code for.
+pub(crate) fn named_fields(data: Data) -> syn::Result<Vec<(Field, FieldConfig)>> {
+    match data {
+        Data::Struct(syn::DataStruct {
+            fields: Fields::Named(f),
+            ..
+        }) => f
+            .named
+            .into_iter()
+            .map(|f| FieldConfig::from_field(&f).map(|c| (f, c)))
+            .collect(),
+        _ => Ok(vec![]),
+    }
+}
+
+/// Fields of a named or tuple struct with their member and configuration, in declaration order
+pub(crate) fn fields(data: Data) -> syn::Result<Vec<(Member, Field, FieldConfig)>> {
+    match data {
+        Data::Struct(s) => members(s.fields),
+        _ => Ok(vec![]),
+    }
+}
+
+/// Fields of a struct or an enum variant with their member and configuration, in declaration
+/// order
+pub(crate) fn members(fields: Fields) -> syn::Result<Vec<(Member, Field, FieldConfig)>> {
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let member = match &f.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(i)),
+            };
+
+            FieldConfig::from_field(&f).map(|c| (member, f, c))
+        })
+        .collect()
+}
+
+pub(crate) type FieldFilter = fn(&Field, &FieldConfig) -> bool;
+
+/// Non-public attributes not marked with `skip`
+pub(crate) fn accessor_field(field: &Field, config: &FieldConfig) -> bool {
+    match field.vis {
+        Visibility::Public(_) => false,
+        _ => !config.skip,
+    }
+}
+
+/// Attributes marked with `get` and not with `skip`
+pub(crate) fn annotated_field(_field: &Field, config: &FieldConfig) -> bool {
+    config.get && !config.skip
+}
+
+/// Accessor attributes not restricted to `set`
+pub(crate) fn readable_field(field: &Field, config: &FieldConfig) -> bool {
+    accessor_field(field, config) && (config.get || config.both || !config.set)
+}
+
+/// Accessor attributes not restricted to `get`
+pub(crate) fn writable_field(field: &Field, config: &FieldConfig) -> bool {
+    accessor_field(field, config) && (config.set || config.both || !config.get)
+}
+
+/// `#[cfg(...)]` and `#[deprecated(...)]` attributes, to be forwarded to the generated accessors of
+/// a field, along with `#[cfg(test)]` for fields marked with `test_only`
+pub(crate) fn accessor_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    let mut accessor: Vec<Attribute> = attrs
+        .iter()
+        .filter(|a| a.path.is_ident("cfg") || a.path.is_ident("deprecated"))
+        .cloned()
+        .collect();
+
+    // Invalid keys are already reported when the configuration of the field is read
+    let test_only = keys(attrs)
+        .unwrap_or_default()
+        .iter()
+        .any(|(k, _)| k == "test_only");
+
+    if test_only {
+        accessor.push(parse_quote!(#[cfg(test)]));
+    }
+
+    accessor
+}
+
+/// `#[cfg(...)]` attributes, to be forwarded to the generated code of a field
+pub(crate) fn cfg_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+/// Name of a key written either as `key` or `unprolix::key`
+fn key(path: &Path) -> Option<Ident> {
+    let segments: Vec<&Ident> = path.segments.iter().map(|s| &s.ident).collect();
+
+    match segments.as_slice() {
+        [k] => Some((*k).clone()),
+        [u, k] if *u == "unprolix" => Some((*k).clone()),
+        _ => None,
+    }
+}
+
+/// Flag key, such as `skip`, which takes no value
+fn flag(key: &Ident, value: &Option<LitStr>) -> syn::Result<bool> {
+    match value {
+        Some(v) => Err(Error::new_spanned(v, format!("{} takes no value", key))),
+        None => Ok(true),
+    }
+}
+
+/// Value of a key that requires one, such as `rename = "name"`
+fn required(key: &Ident, value: Option<LitStr>) -> syn::Result<LitStr> {
+    value.ok_or_else(|| Error::new_spanned(key, format!("expected {} = \"...\"", key)))
+}
+
+/// Keys of the `#[unprolix(...)]` attributes, in the order they are declared
+pub(crate) fn key_names(attrs: &[Attribute]) -> syn::Result<Vec<Ident>> {
+    Ok(keys(attrs)?.into_iter().map(|(k, _)| k).collect())
+}
+
+/// String value of a `key = "value"` key
+fn value(nv: &MetaNameValue) -> syn::Result<LitStr> {
+    match &nv.lit {
+        Lit::Str(s) => Ok(s.clone()),
+        lit => {
+            let key = nv.path.segments.last().map(|s| s.ident.to_string());
+
+            Err(Error::new_spanned(
+                lit,
+                format!(
+                    "{} expects a string literal, such as {0} = \"value\"",
+                    key.unwrap_or_default()
+                ),
+            ))
+        }
+    }
+}
+
+/// Keys listed by a `#[unprolix(key(a = "value", b))]` key, with their string value if any
+fn nested(attrs: &[Attribute], name: &str) -> syn::Result<Vec<(Ident, Option<LitStr>)>> {
+    let mut keys = vec![];
+
+    for a in attrs.iter().filter(|a| a.path.is_ident("unprolix")) {
+        if let Meta::List(l) = a.parse_meta()? {
+            for n in l.nested.iter() {
+                match n {
+                    NestedMeta::Meta(Meta::List(l)) if l.path.is_ident(name) => {
+                        for n in l.nested.iter() {
+                            let (k, value) = match n {
+                                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                                    (nv.path.get_ident(), Some(value(nv)?))
+                                }
+                                NestedMeta::Meta(Meta::Path(p)) => (p.get_ident(), None),
+                                _ => (None, None),
+                            };
+
+                            match k {
+                                Some(k) => keys.push((k.clone(), value)),
+                                None => {
+                                    return Err(Error::new_spanned(
+                                        n,
+                                        format!(
+                                        "{} expects attribute names, such as {0}(a, b = \"name\")",
+                                        name
+                                    ),
+                                    ))
+                                }
+                            }
+                        }
+                    }
+                    // The other keys are read by `keys`
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Every key, with its string value if any, in the order they are declared
+fn keys(attrs: &[Attribute]) -> syn::Result<Vec<(Ident, Option<LitStr>)>> {
+    let mut keys = vec![];
+
+    for a in attrs.iter().filter(|a| a.path.is_ident("unprolix")) {
+        let l = match a.parse_meta()? {
+            Meta::List(l) => l,
+            m => {
+                return Err(Error::new_spanned(
+                    m,
+                    "expected a list of keys, such as #[unprolix(skip)]",
+                ))
+            }
+        };
+
+        for n in l.nested.iter() {
+            let (k, value) = match n {
+                NestedMeta::Meta(Meta::NameValue(nv)) => (key(&nv.path), Some(value(nv)?)),
+                // Only `flatten` lists keys, which are read by `nested`
+                NestedMeta::Meta(Meta::List(l)) if !l.path.is_ident("flatten") => {
+                    return Err(Error::new_spanned(
+                        l,
+                        match key(&l.path) {
+                            Some(k) => format!("{} takes no list", k),
+                            None => String::from("expected a key, such as skip"),
+                        },
+                    ))
+                }
+                NestedMeta::Meta(m) => (key(m.path()), None),
+                NestedMeta::Lit(_) => (None, None),
+            };
+
+            match k {
+                Some(k) => keys.push((k, value)),
+                None => return Err(Error::new_spanned(n, "expected a key, such as skip")),
+            }
+        }
+    }
+
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::DeriveInput;
+
+    #[test]
+    fn foreign_attributes_ignored() {
+        let input: DeriveInput = parse_quote! {
+            struct Foreign {
+                #[serde(skip, default)]
+                #[serde(rename = "b", default = "default_a")]
+                a: u8,
+                #[serde(default)]
+                #[unprolix(unprolix::copy)]
+                c: u8,
+            }
+        };
+        let fields = named_fields(input.data).unwrap();
+
+        let (_, a) = &fields[0];
+        assert!(!a.skip && !a.default && a.default_value.is_none() && a.rename.is_none());
+
+        let (_, c) = &fields[1];
+        assert!(!c.default && c.getter_kind == Some(GetterKind::Copy));
+    }
+
+    #[test]
+    fn non_string_value_rejected() {
+        let input: DeriveInput = parse_quote! {
+            struct Counter {
+                #[unprolix(default = 5)]
+                a: u8,
+            }
+        };
+        let e = named_fields(input.data).err().unwrap();
+
+        assert_eq!(
+            "default expects a string literal, such as default = \"value\"",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn unknown_keys_rejected() {
+        let input: DeriveInput = parse_quote! {
+            struct Counter {
+                #[unprolix(coppy)]
+                a: u8,
+            }
+        };
+        let e = named_fields(input.data).err().unwrap();
+        assert_eq!("unknown attribute key coppy", e.to_string());
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[unprolix(trait_name = "Api")])];
+        let e = ContainerConfig::from_attributes(&attrs).err().unwrap();
+        assert_eq!("unknown container key trait_name", e.to_string());
+    }
+    #[test]
+    fn missing_and_unexpected_values_rejected() {
+        let error = |input: DeriveInput| named_fields(input.data).err().unwrap().to_string();
+
+        assert_eq!(
+            "expected rename = \"...\"",
+            error(parse_quote! {
+                struct Counter {
+                    #[unprolix(rename)]
+                    a: u8,
+                }
+            })
+        );
+        assert_eq!(
+            "get takes no value",
+            error(parse_quote! {
+                struct Counter {
+                    #[unprolix(get = "a")]
+                    a: u8,
+                }
+            })
+        );
+        assert_eq!(
+            "copy takes no list",
+            error(parse_quote! {
+                struct Counter {
+                    #[unprolix(copy(a))]
+                    a: u8,
+                }
+            })
+        );
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[unprolix(error)])];
+        let e = ContainerConfig::from_attributes(&attrs).err().unwrap();
+        assert_eq!("expected error = \"...\"", e.to_string());
+    }
+}
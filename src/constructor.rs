@@ -0,0 +1,1173 @@
+use crate::case::Case;
+use crate::config::{cfg_attrs, named_fields, ContainerConfig};
+use crate::ty::{const_default, lacks_default, mentions, phantom, type_argument, used_generics};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, Attribute, DeriveInput, Error, Expr, Field, FieldValue, Generics, Ident, Member,
+    Token, Type, Visibility, WhereClause, WherePredicate,
+};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let vis = input.vis;
+
+    let container = ContainerConfig::from_attributes(&input.attrs)?;
+    let named_args = container.named_args;
+    let const_fn = container.const_fn || container.const_default;
+    let defaults_last = container.defaults_last;
+
+    if container.required_ref && (named_args || const_fn) {
+        return Err(Error::new(
+            Span::call_site(),
+            "required_ref is not supported with named_args or const_fn",
+        ));
+    }
+
+    if container.checked_unchecked && (container.try_new || container.const_default) {
+        return Err(Error::new(
+            Span::call_site(),
+            "checked_unchecked is not supported with try_new or const_default",
+        ));
+    }
+
+    let mut skipped: Punctuated<FieldValue, Token![,]> = Punctuated::new();
+    let mut skipped_types: Vec<Type> = vec![];
+    let mut uninit: Vec<Ident> = vec![];
+    let mut values: Punctuated<FieldValue, Token![,]> = Punctuated::new();
+    let mut defaults: Punctuated<FieldValue, Token![,]> = Punctuated::new();
+    let mut args: Punctuated<Field, Token![,]> = Punctuated::new();
+    let mut collected: Vec<(Ident, Type)> = vec![];
+    let mut bounds: Vec<WherePredicate> = vec![];
+    let mut instance: Punctuated<FieldValue, Token![,]> = Punctuated::new();
+    let mut validations: Vec<(TokenStream, TokenStream)> = vec![];
+    let mut variants: Vec<TokenStream> = vec![];
+    let mut required: Vec<Parameter> = vec![];
+    let mut optional: Vec<Parameter> = vec![];
+    let mut const_args: Vec<(Ident, Expr)> = vec![];
+    let mut into_params: Vec<TokenStream> = vec![];
+
+    let error_name: Ident = syn::parse_str(format!("{}Error", name).as_str()).unwrap();
+
+    let params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+
+    let fields = named_fields(input.data)?;
+    let count = fields.len();
+
+    for (mut field, config) in fields {
+        let ident = field.ident.as_ref().cloned().unwrap();
+        let cfg = cfg_attrs(&field.attrs);
+
+        // Name of the parameter of `new`, distinct from the attribute with `arg = "name"`
+        let param: Ident = match &config.arg {
+            Some(arg) => arg.parse()?,
+            None => ident.clone(),
+        };
+
+        if config.uninit {
+            if config.arg.is_some() {
+                return Err(Error::new_spanned(
+                    &field,
+                    "arg is not supported with uninit",
+                ));
+            }
+
+            uninit.push(ident.clone());
+        }
+
+        if config.default && config.default_value.is_none() && lacks_default(&field.ty) {
+            return Err(Error::new_spanned(
+                &field.ty,
+                "this type doesn't implement Default; provide a value with #[unprolix(default = \"expr\")]",
+            ));
+        }
+
+        if let Some(validate) = &config.validate {
+            let validate: Expr = validate.parse()?;
+
+            let validation = quote! {
+                #(#cfg)*
+                (#validate)(&value.#ident)
+            };
+
+            // Every validated attribute maps its error to its own variant
+            let mapping = if container.gen_error {
+                let variant = Ident::new(&Case::Pascal.apply(&ident.to_string()), ident.span());
+
+                match &config.error {
+                    Some(ty) => {
+                        let ty: Type = ty.parse()?;
+
+                        variants.push(quote! { #(#cfg)* #variant(#ty) });
+                        quote! { .map_err(#error_name::#variant) }
+                    }
+                    None => {
+                        variants.push(quote! { #(#cfg)* #variant });
+                        quote! { .map_err(|_| #error_name::#variant) }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            validations.push((validation, mapping));
+        }
+
+        if container.singleton {
+            let expr = match (config.default, config.overridable, &config.default_value) {
+                (true, false, Some(v)) => v.parse()?,
+                (true, false, None) => match const_default(&field.ty) {
+                    Some(expr) => expr,
+                    None => {
+                        return Err(Error::new_spanned(
+                            &field,
+                            format!(
+                                "singleton requires an explicit default = \"expr\" for {}, since its type is not a primitive",
+                                ident
+                            ),
+                        ))
+                    }
+                },
+                (true, true, _) => {
+                    return Err(Error::new_spanned(
+                        &field,
+                        format!(
+                            "singleton requires every default to be fixed, but {} is overridable",
+                            ident
+                        ),
+                    ))
+                }
+                (false, _, _) => {
+                    return Err(Error::new_spanned(
+                        &field,
+                        format!(
+                            "singleton requires every attribute to be defaulted, but {} is not",
+                            ident
+                        ),
+                    ))
+                }
+            };
+
+            instance.push(FieldValue {
+                attrs: cfg.clone(),
+                member: Member::Named(ident.clone()),
+                colon_token: Some(<Token![:]>::default()),
+                expr,
+            });
+        }
+
+        if container.partial && !config.skip {
+            if config.from_slice || config.collect {
+                return Err(Error::new_spanned(
+                    &field,
+                    "from_slice and collect are not supported with partial",
+                ));
+            }
+
+            let parameter = Parameter {
+                attrs: cfg.clone(),
+                ident: ident.clone(),
+                param: param.clone(),
+                ty: field.ty.clone(),
+                default: config.default_expr(&field.ty)?,
+            };
+
+            if config.default {
+                optional.push(parameter);
+            } else {
+                required.push(parameter);
+            }
+        }
+
+        if const_fn && (config.overridable || config.from_slice || config.collect) {
+            return Err(Error::new_spanned(
+                &field,
+                "overridable, from_slice and collect are not supported with const_fn",
+            ));
+        }
+
+        let const_value = if const_fn && config.default && config.default_value.is_none() {
+            match const_default(&field.ty) {
+                Some(expr) => Some(expr),
+                None => {
+                    return Err(Error::new_spanned(
+                        &field,
+                        "const_fn requires an explicit default = \"expr\" for defaulted attributes that are not primitives",
+                    ))
+                }
+            }
+        } else {
+            None
+        };
+
+        // Only the defaulted types are bounded, so `Vec<T>` doesn't require `T: Default`
+        if config.default && config.default_value.is_none() && mentions(&field.ty, &params) {
+            let ty = &field.ty;
+
+            bounds.push(parse_quote!(#ty: ::core::default::Default));
+        }
+
+        if config.skip {
+            if const_fn {
+                return Err(Error::new_spanned(
+                    &field,
+                    "skip is not supported with const_fn",
+                ));
+            }
+
+            skipped.push(FieldValue {
+                attrs: cfg.clone(),
+                member: Member::Named(ident.clone()),
+                colon_token: Some(<Token![:]>::default()),
+                expr: parse_quote!(::core::default::Default::default()),
+            });
+            skipped_types.push(field.ty.clone());
+        } else if config.default && config.overridable {
+            let default = config.default_expr(&field.ty)?;
+            let fv = FieldValue {
+                attrs: cfg.clone(),
+                member: Member::Named(ident.clone()),
+                colon_token: Some(<Token![:]>::default()),
+                expr: parse_quote!(#param.unwrap_or_else(|| #default)),
+            };
+            if defaults_last {
+                defaults.push(fv);
+            } else {
+                values.push(fv);
+            }
+
+            let ty = &field.ty;
+            let ty: Type = parse_quote!(::core::option::Option<#ty>);
+
+            field.attrs = cfg;
+            field.ident = Some(param);
+            field.vis = Visibility::Inherited;
+            field.colon_token = None;
+            field.ty = ty;
+
+            args.push(field);
+        } else if config.default {
+            let expr = match const_value {
+                Some(expr) => expr,
+                None => config.default_expr(&field.ty)?,
+            };
+
+            let fv = FieldValue {
+                attrs: cfg.clone(),
+                member: Member::Named(ident.clone()),
+                colon_token: Some(<Token![:]>::default()),
+                expr,
+            };
+            if defaults_last {
+                defaults.push(fv);
+            } else {
+                values.push(fv);
+            }
+        } else if config.from_slice {
+            if named_args {
+                return Err(Error::new_spanned(
+                    &field,
+                    "from_slice is not supported with named_args",
+                ));
+            }
+
+            let ty = match type_argument(&field.ty, "Vec") {
+                Some(ty) => ty,
+                None => {
+                    return Err(Error::new_spanned(
+                        &field.ty,
+                        "from_slice is expected only for Vec types",
+                    ))
+                }
+            };
+
+            let fv = FieldValue {
+                attrs: cfg.clone(),
+                member: Member::Named(ident.clone()),
+                colon_token: Some(<Token![:]>::default()),
+                expr: parse_quote!(#param.to_vec()),
+            };
+            values.push(fv);
+
+            field.attrs = cfg;
+            field.ident = Some(param);
+            field.vis = Visibility::Inherited;
+            field.colon_token = None;
+            field.ty = parse_quote!(&[#ty]);
+
+            args.push(field);
+        } else if config.collect {
+            if named_args {
+                return Err(Error::new_spanned(
+                    &field,
+                    "collect is not supported with named_args",
+                ));
+            }
+
+            let ty = match ["Vec", "HashSet"]
+                .iter()
+                .find_map(|w| type_argument(&field.ty, w))
+            {
+                Some(ty) => ty,
+                None => {
+                    return Err(Error::new_spanned(
+                        &field.ty,
+                        "collect is expected only for Vec or HashSet types",
+                    ))
+                }
+            };
+
+            let fv = FieldValue {
+                attrs: cfg.clone(),
+                member: Member::Named(ident.clone()),
+                colon_token: Some(<Token![:]>::default()),
+                expr: parse_quote!(#param.into_iter().collect()),
+            };
+            values.push(fv);
+
+            // The collection itself is a default iterator for the Default implementation
+            collected.push((param.clone(), field.ty.clone()));
+
+            field.attrs = cfg;
+            field.ident = Some(param);
+            field.vis = Visibility::Inherited;
+            field.colon_token = None;
+            field.ty = parse_quote!(impl ::core::iter::IntoIterator<Item = #ty>);
+
+            args.push(field);
+        } else if config.into || container.into {
+            if named_args || const_fn || container.required_ref {
+                return Err(Error::new_spanned(
+                    &field,
+                    "into is not supported with named_args, const_fn or required_ref",
+                ));
+            }
+
+            let fv = FieldValue {
+                attrs: cfg.clone(),
+                member: Member::Named(ident.clone()),
+                colon_token: Some(<Token![:]>::default()),
+                expr: parse_quote!(#param.into()),
+            };
+            values.push(fv);
+
+            // The attribute type itself converts into the attribute for the Default implementation
+            collected.push((param.clone(), field.ty.clone()));
+
+            // With `where_clause`, the argument is a named type parameter of `new`
+            let ty = &field.ty;
+            let into: Type = if container.where_clause {
+                let generic = format_ident!("__{}", Case::Pascal.apply(&param.to_string()));
+
+                into_params.push(quote! { #(#cfg)* #generic: ::core::convert::Into<#ty> });
+
+                parse_quote!(#generic)
+            } else {
+                parse_quote!(impl ::core::convert::Into<#ty>)
+            };
+
+            field.attrs = cfg;
+            field.ident = Some(param);
+            field.vis = Visibility::Inherited;
+            field.colon_token = None;
+            field.ty = into;
+
+            args.push(field);
+        } else {
+            if container.const_default {
+                match const_default(&field.ty) {
+                    Some(expr) => const_args.push((param.clone(), expr)),
+                    None => {
+                        return Err(Error::new_spanned(
+                            &field,
+                            "const_default requires the arguments of new to be primitives",
+                        ))
+                    }
+                }
+            }
+
+            let fv = if container.required_ref {
+                FieldValue {
+                    attrs: cfg.clone(),
+                    member: Member::Named(ident.clone()),
+                    colon_token: Some(<Token![:]>::default()),
+                    expr: parse_quote!(#param.clone()),
+                }
+            } else {
+                FieldValue {
+                    attrs: cfg.clone(),
+                    member: Member::Named(ident.clone()),
+                    colon_token: config.arg.as_ref().map(|_| <Token![:]>::default()),
+                    expr: Expr::Verbatim(param.to_token_stream()),
+                }
+            };
+            values.push(fv);
+
+            if container.required_ref {
+                // The Default implementation clones an owned default value instead
+                collected.push((param.clone(), field.ty.clone()));
+
+                let ty = &field.ty;
+                field.ty = parse_quote!(&#ty);
+            }
+
+            field.attrs = cfg;
+            field.ident = Some(param);
+            field.vis = Visibility::Inherited;
+            field.colon_token = None;
+
+            args.push(field);
+        }
+    }
+
+    values.extend(defaults);
+
+    // Every attribute is either set by `new` or filled by the `Default` tail, exactly once
+    if values.len() + skipped.len() != count {
+        return Err(Error::new(
+            Span::call_site(),
+            format!(
+                "internal error: {} attributes were expected to be initialized, but {} were",
+                count,
+                values.len() + skipped.len()
+            ),
+        ));
+    }
+
+    let rest = if !skipped.is_empty() {
+        if !values.empty_or_trailing() {
+            values.push_punct(<Token![,]>::default());
+        }
+
+        quote! { ..::core::default::Default::default() }
+    } else {
+        quote! {}
+    };
+
+    let fn_generics = if into_params.is_empty() {
+        quote! {}
+    } else {
+        quote! { <#(#into_params),*> }
+    };
+
+    let new_uninit = new_uninit(&fn_generics, &args, &values, &rest, &uninit)?;
+    let mut bounded = generics.clone();
+    if !bounds.is_empty() {
+        bounded.make_where_clause().predicates.extend(bounds);
+    }
+    // The derived `Default` fills the skipped attributes one by one
+    let mut default_bounded = bounded.clone();
+    // The `Default` tail of the skipped attributes requires the instance to implement it
+    if !skipped.is_empty() {
+        bounded
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#name #ty_generics: ::core::default::Default));
+    }
+    let (_, _, bounded_where) = bounded.split_for_impl();
+
+    let constness = if const_fn {
+        quote! { const }
+    } else {
+        quote! {}
+    };
+
+    let inline = if container.inline {
+        quote! { #[inline] }
+    } else {
+        quote! {}
+    };
+
+    // With `checked_unchecked`, `new` is the validated function and the plain one is `unsafe`
+    let (new, unsafety, safety) = if container.checked_unchecked {
+        (
+            quote! { new_unchecked },
+            quote! { unsafe },
+            quote! {
+                /// Create an instance without running the validation functions of the attributes
+                ///
+                /// # Safety
+                ///
+                /// The arguments must be accepted by the validation functions, since the
+                /// instance is otherwise invalid
+            },
+        )
+    } else {
+        (quote! { new }, quote! {}, quote! {})
+    };
+
+    let generated = if container.gen_error {
+        if !container.try_new && !container.checked_unchecked {
+            return Err(Error::new(
+                Span::call_site(),
+                "gen_error requires try_new or checked_unchecked",
+            ));
+        }
+
+        Some(&error_name)
+    } else {
+        None
+    };
+
+    let expanded = if named_args {
+        let args_name: Ident = syn::parse_str(format!("{}Args", name).as_str()).unwrap();
+        let idents: Vec<TokenStream> = args
+            .iter()
+            .map(|f| {
+                let attrs = &f.attrs;
+                let ident = &f.ident;
+
+                quote! { #(#attrs)* #ident }
+            })
+            .collect();
+
+        args.iter_mut().for_each(|f| f.vis = parse_quote!(pub));
+
+        // Parameters used only by the defaulted or skipped attributes are left out of the arguments
+        let args_generics = used_generics(generics, args.iter().map(|f| &f.ty));
+        let (_, args_ty_generics, args_where) = args_generics.split_for_impl();
+
+        let try_new = try_new(
+            &container,
+            &quote! {},
+            quote! { (args: #args_name #args_ty_generics) },
+            quote! {
+                let #args_name { #(#idents),* } = args;
+
+                Self {
+                    #values
+                    #rest
+                }
+            },
+            &validations,
+            generated,
+        )?;
+
+        quote! {
+            #vis struct #args_name #args_generics #args_where {
+                #args
+            }
+
+            impl #impl_generics #name #ty_generics #bounded_where {
+                #safety
+                #inline
+                pub #constness #unsafety fn #new(args: #args_name #args_ty_generics) -> Self {
+                    let #args_name { #(#idents),* } = args;
+
+                    Self {
+                        #values
+                        #rest
+                    }
+                }
+
+                #try_new
+            }
+        }
+    } else {
+        let mut allow = too_many_arguments(args.len());
+
+        // `&String` or `&Vec<T>` arguments follow the attributes as well
+        if container.required_ref {
+            allow.extend(quote! { #[allow(clippy::ptr_arg)] });
+        }
+
+        let try_new = try_new(
+            &container,
+            &allow,
+            quote! { #fn_generics(#args) },
+            quote! {
+                Self {
+                    #values
+                    #rest
+                }
+            },
+            &validations,
+            generated,
+        )?;
+
+        // An argument-free `new` is expected to come with a Default implementation, which is
+        // generated only with `derive_default`
+        let without_default = if args.is_empty() && !container.derive_default {
+            quote! { #[allow(clippy::new_without_default)] }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            impl #impl_generics #name #ty_generics #bounded_where {
+                #safety
+                #allow
+                #without_default
+                #inline
+                pub #constness #unsafety fn #new #fn_generics(#args) -> Self {
+                    Self {
+                        #values
+                        #rest
+                    }
+                }
+
+                #try_new
+            }
+        }
+    };
+
+    let instance = if container.singleton {
+        quote! {
+            /// Instance with every attribute set to its default value
+            pub const INSTANCE: Self = Self { #instance };
+        }
+    } else {
+        quote! {}
+    };
+
+    let const_default = if container.const_default {
+        let (params, exprs): (Vec<_>, Vec<_>) = const_args.into_iter().unzip();
+        let call = if named_args {
+            let args_name: Ident = syn::parse_str(format!("{}Args", name).as_str()).unwrap();
+
+            quote! { Self::new(#args_name { #(#params: #exprs),* }) }
+        } else {
+            quote! { Self::new(#(#exprs),*) }
+        };
+
+        quote! {
+            /// Instance created by `new` with the zero value of every argument
+            pub const DEFAULT: Self = #call;
+        }
+    } else {
+        quote! {}
+    };
+
+    let default = if container.derive_default {
+        // The validated `new` of `checked_unchecked` returns a `Result`
+        let body = if args.is_empty() && skipped.is_empty() && !container.checked_unchecked {
+            if named_args {
+                let args_name: Ident = syn::parse_str(format!("{}Args", name).as_str()).unwrap();
+
+                quote! { Self::new(#args_name {}) }
+            } else {
+                quote! { Self::new() }
+            }
+        } else {
+            // `new` can't be called without arguments, or would call back this implementation
+            // to fill the skipped attributes
+            let types: Vec<&Type> = args
+                .iter()
+                .map(|f| {
+                    collected
+                        .iter()
+                        .find(|(i, _)| Some(i) == f.ident.as_ref())
+                        .map(|(_, ty)| ty)
+                        .unwrap_or(&f.ty)
+                })
+                .collect();
+            let lets = args.iter().zip(types.iter()).map(|(f, ty)| {
+                let attrs = &f.attrs;
+                let ident = &f.ident;
+
+                quote! { #(#attrs)* let #ident: #ty = ::core::default::Default::default(); }
+            });
+
+            // Every argument and skipped attribute of a generic type is filled by its default
+            let bounds = types
+                .iter()
+                .copied()
+                .chain(skipped_types.iter())
+                .filter(|ty| mentions(ty, &params))
+                .map(|ty| -> WherePredicate { parse_quote!(#ty: ::core::default::Default) });
+
+            default_bounded
+                .make_where_clause()
+                .predicates
+                .extend(bounds);
+
+            quote! {
+                #(#lets)*
+
+                Self {
+                    #values
+                    #skipped
+                }
+            }
+        };
+
+        let default_where = &default_bounded.where_clause;
+
+        quote! {
+            impl #impl_generics ::core::default::Default for #name #ty_generics #default_where {
+                fn default() -> Self {
+                    #body
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let partial = if container.partial {
+        partial(
+            &vis,
+            &name,
+            generics,
+            &bounded_where,
+            &required,
+            &optional,
+            &rest,
+        )
+    } else {
+        quote! {}
+    };
+
+    let error_enum = if container.gen_error {
+        quote! {
+            /// Validation errors of the constructor, one variant per validated attribute
+            #[derive(Debug)]
+            #vis enum #error_name {
+                #(#variants),*
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #error_enum
+
+        #expanded
+
+        #partial
+
+        impl #impl_generics #name #ty_generics #bounded_where {
+            #instance
+            #const_default
+            #new_uninit
+        }
+
+        #default
+    })
+}
+
+/// Parameter of `new_partial`, or optional attribute of the partial struct
+struct Parameter {
+    attrs: Vec<Attribute>,
+    ident: Ident,
+    param: Ident,
+    ty: Type,
+    default: Expr,
+}
+
+/// `PartialName` struct, created by `new_partial` with the required attributes, with a setter for
+/// each defaulted attribute and a `finish` function building the instance
+fn partial(
+    vis: &Visibility,
+    name: &Ident,
+    generics: &Generics,
+    bounded_where: &Option<&WhereClause>,
+    required: &[Parameter],
+    optional: &[Parameter],
+    rest: &TokenStream,
+) -> TokenStream {
+    let partial_name: Ident = syn::parse_str(format!("Partial{}", name).as_str()).unwrap();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = required.iter().map(|p| {
+        let Parameter {
+            attrs, param, ty, ..
+        } = p;
+
+        quote! { #(#attrs)* #param: #ty }
+    });
+    let optional_fields = optional.iter().map(|p| {
+        let Parameter {
+            attrs, param, ty, ..
+        } = p;
+
+        quote! { #(#attrs)* #param: ::core::option::Option<#ty> }
+    });
+
+    let params: Vec<TokenStream> = required
+        .iter()
+        .chain(optional.iter())
+        .map(|p| {
+            let Parameter { attrs, param, .. } = p;
+
+            quote! { #(#attrs)* #param }
+        })
+        .collect();
+    let args = fields.clone();
+    let shorthand = required.iter().map(|p| {
+        let Parameter { attrs, param, .. } = p;
+
+        quote! { #(#attrs)* #param, }
+    });
+    let initial = optional.iter().map(|p| {
+        let Parameter { attrs, param, .. } = p;
+
+        quote! { #(#attrs)* #param: ::core::option::Option::None, }
+    });
+
+    let setters = optional.iter().map(|p| {
+        let Parameter {
+            attrs, param, ty, ..
+        } = p;
+
+        quote! {
+            #(#attrs)*
+            pub fn #param(mut self, v: #ty) -> Self {
+                self.#param = ::core::option::Option::Some(v);
+                self
+            }
+        }
+    });
+
+    let values = required.iter().map(|p| {
+        let Parameter {
+            attrs,
+            ident,
+            param,
+            ..
+        } = p;
+
+        quote! { #(#attrs)* #ident: #param, }
+    });
+    let defaults = optional.iter().map(|p| {
+        let Parameter {
+            attrs,
+            ident,
+            param,
+            default,
+            ..
+        } = p;
+
+        quote! { #(#attrs)* #ident: #param.unwrap_or_else(|| #default), }
+    });
+    let allow = too_many_arguments(required.len());
+
+    // A type parameter used only by a skipped attribute must still be used by the struct
+    let (marker, initial_marker) = match phantom(generics) {
+        Some(phantom) => (
+            quote! { __marker: #phantom, },
+            quote! { __marker: ::core::marker::PhantomData, },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+
+    quote! {
+        #vis struct #partial_name #generics #where_clause {
+            #(#fields,)*
+            #(#optional_fields,)*
+            #marker
+        }
+
+        impl #impl_generics #name #ty_generics #bounded_where {
+            #allow
+            pub fn new_partial(#(#args),*) -> #partial_name #ty_generics {
+                #partial_name {
+                    #(#shorthand)*
+                    #(#initial)*
+                    #initial_marker
+                }
+            }
+        }
+
+        impl #impl_generics #partial_name #ty_generics #bounded_where {
+            #(#setters)*
+
+            pub fn finish(self) -> #name #ty_generics {
+                let #partial_name { #(#params,)* .. } = self;
+
+                #name {
+                    #(#values)*
+                    #(#defaults)*
+                    #rest
+                }
+            }
+        }
+    }
+}
+
+/// `try_new` function, or `new` with `checked_unchecked`, building the instance from `body` and
+/// then running the `validate` functions of the attributes
+///
+/// The error type is the generated `error` enum, if any, or otherwise the one of the container.
+/// `params` are the parenthesized parameters, preceded by the type parameters of `new` if any.
+fn try_new(
+    container: &ContainerConfig,
+    allow: &TokenStream,
+    params: TokenStream,
+    body: TokenStream,
+    validations: &[(TokenStream, TokenStream)],
+    error: Option<&Ident>,
+) -> syn::Result<TokenStream> {
+    let (method, key) = if container.checked_unchecked {
+        (quote! { new }, "checked_unchecked")
+    } else if container.try_new {
+        (quote! { try_new }, "try_new")
+    } else {
+        return Ok(quote! {});
+    };
+
+    let error: Type = match (error, &container.error) {
+        (Some(error), _) => parse_quote!(#error),
+        (None, Some(error)) => error.parse()?,
+        (None, None) => {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "{} requires the error type, provided with error = \"Type\" or gen_error",
+                    key
+                ),
+            ))
+        }
+    };
+    let (validations, mappings): (Vec<_>, Vec<_>) = validations.iter().cloned().unzip();
+
+    let (asyncness, awaited) = if container.async_ {
+        (quote! { async }, quote! { .await })
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // The caller location is not tracked through the future of an `async fn`
+    let track_caller = if container.track_caller && !container.async_ {
+        quote! { #[track_caller] }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #allow
+        #track_caller
+        pub #asyncness fn #method #params -> ::core::result::Result<Self, #error> {
+            let value = { #body };
+
+            #(#validations #awaited #mappings?;)*
+
+            ::core::result::Result::Ok(value)
+        }
+    })
+}
+
+/// Silence the Clippy lint for the generated functions of structs with many attributes, since the
+/// arguments follow the attributes
+fn too_many_arguments(count: usize) -> TokenStream {
+    if count > 7 {
+        quote! { #[allow(clippy::too_many_arguments)] }
+    } else {
+        quote! {}
+    }
+}
+
+#[cfg(feature = "unsafe-uninit")]
+fn new_uninit(
+    generics: &TokenStream,
+    args: &Punctuated<Field, Token![,]>,
+    values: &Punctuated<FieldValue, Token![,]>,
+    rest: &TokenStream,
+    uninit: &[Ident],
+) -> syn::Result<TokenStream> {
+    if uninit.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let args: Punctuated<&Field, Token![,]> = args
+        .iter()
+        .filter(|f| !uninit.contains(f.ident.as_ref().unwrap()))
+        .collect();
+    let allow = too_many_arguments(args.len());
+
+    let values: Punctuated<FieldValue, Token![,]> = values
+        .iter()
+        .cloned()
+        .map(|mut fv| {
+            match &fv.member {
+                Member::Named(i) if uninit.contains(i) => {
+                    fv.colon_token = Some(<Token![:]>::default());
+                    fv.expr = parse_quote! {
+                        unsafe { ::core::mem::MaybeUninit::uninit().assume_init() }
+                    };
+                }
+                _ => (),
+            }
+            fv
+        })
+        .collect();
+
+    Ok(quote! {
+        /// Create a new instance, leaving the `uninit` attributes uninitialized
+        ///
+        /// # Safety
+        ///
+        /// Every `uninit` attribute must be a type that is valid while uninitialized, such as
+        /// `MaybeUninit<T>` or `[MaybeUninit<T>; N]`.
+        #allow
+        pub unsafe fn new_uninit #generics(#args) -> Self {
+            Self {
+                #values
+                #rest
+            }
+        }
+    })
+}
+
+#[cfg(not(feature = "unsafe-uninit"))]
+fn new_uninit(
+    _generics: &TokenStream,
+    _args: &Punctuated<Field, Token![,]>,
+    _values: &Punctuated<FieldValue, Token![,]>,
+    _rest: &TokenStream,
+    uninit: &[Ident],
+) -> syn::Result<TokenStream> {
+    match uninit.first() {
+        Some(ident) => Err(Error::new_spanned(
+            ident,
+            "uninit requires the unsafe-uninit feature",
+        )),
+        None => Ok(quote! {}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{ImplItem, Item, Stmt};
+
+    /// Attributes set by the struct literal of `new`, and whether it has a `Default` tail
+    fn literal(expanded: TokenStream) -> (Vec<String>, bool) {
+        let file: syn::File = syn::parse2(expanded).unwrap();
+        let new = file
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                Item::Impl(i) => Some(i.items.iter()),
+                _ => None,
+            })
+            .flatten()
+            .find_map(|i| match i {
+                ImplItem::Method(m) if m.sig.ident == "new" => Some(m),
+                _ => None,
+            })
+            .unwrap();
+
+        match new.block.stmts.last() {
+            Some(Stmt::Expr(Expr::Struct(s))) => (
+                s.fields
+                    .iter()
+                    .map(|f| match &f.member {
+                        Member::Named(i) => i.to_string(),
+                        Member::Unnamed(i) => i.index.to_string(),
+                    })
+                    .collect(),
+                s.rest.is_some(),
+            ),
+            _ => panic!("new is expected to end with a struct literal"),
+        }
+    }
+
+    #[test]
+    fn every_attribute_initialized_once() {
+        let (members, rest) = literal(
+            expand(parse_quote! {
+                struct Session {
+                    user: String,
+                    #[unprolix(skip)]
+                    token: Option<String>,
+                    #[unprolix(default = "30")]
+                    timeout: u32,
+                    #[unprolix(default, overridable)]
+                    retries: u8,
+                    #[unprolix(into)]
+                    host: String,
+                }
+            })
+            .unwrap(),
+        );
+
+        assert_eq!(vec!["user", "timeout", "retries", "host"], members);
+        assert!(rest);
+
+        let (members, rest) = literal(
+            expand(parse_quote! {
+                #[unprolix(named_args)]
+                struct Pair {
+                    #[unprolix(default)]
+                    a: u8,
+                    b: u16,
+                }
+            })
+            .unwrap(),
+        );
+
+        assert_eq!(vec!["a", "b"], members);
+        assert!(!rest);
+    }
+
+    #[test]
+    fn defaults_last_order() {
+        let expanded = expand(parse_quote! {
+            #[unprolix(defaults_last)]
+            struct Profile {
+                #[unprolix(default = "age >= 18")]
+                adult: bool,
+                age: u8,
+                #[unprolix(default, overridable)]
+                nickname: Option<String>,
+                score: u32,
+            }
+        })
+        .unwrap()
+        .to_string()
+        .replace(' ', "");
+
+        // The arguments follow the declaration order, and the defaulted attributes are last in
+        // the literal
+        assert!(expanded.contains(concat!(
+            "pubfnnew(age:u8,nickname:::core::option::Option<Option<String>>,score:u32)->Self{",
+            "Self{age,score,adult:age>=18,nickname:nickname.unwrap_or_else(",
+        )));
+    }
+
+    #[test]
+    fn inline() {
+        let expanded = expand(parse_quote! {
+            #[unprolix(inline)]
+            struct Brush {
+                width: u8,
+            }
+        })
+        .unwrap()
+        .to_string()
+        .replace(' ', "");
+
+        assert!(expanded.contains("#[inline]pubfnnew(width:u8)->Self"));
+    }
+    #[test]
+    fn singleton_errors_name_the_attribute() {
+        let error = |input| expand(input).err().unwrap().to_string();
+
+        assert_eq!(
+            "singleton requires every default to be fixed, but level is overridable",
+            error(parse_quote! {
+                #[unprolix(singleton)]
+                struct Volume {
+                    #[unprolix(default)]
+                    muted: bool,
+                    #[unprolix(default, overridable)]
+                    level: u8,
+                }
+            })
+        );
+        assert_eq!(
+            "singleton requires every attribute to be defaulted, but level is not",
+            error(parse_quote! {
+                #[unprolix(singleton)]
+                struct Volume {
+                    level: u8,
+                }
+            })
+        );
+    }
+}
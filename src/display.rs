@@ -0,0 +1,37 @@
+use crate::config::fields;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{DeriveInput, Error};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = fields(input.data)?;
+    let mut marked = fields.iter().filter(|(_, _, config)| config.display);
+
+    // A single attribute is the delegate even if not marked, as in newtypes
+    let member = match (marked.next(), marked.next(), fields.as_slice()) {
+        (Some(_), Some((_, field, _)), _) => {
+            return Err(Error::new_spanned(
+                field,
+                "display is expected on a single attribute",
+            ))
+        }
+        (Some((member, _, _)), None, _) | (None, None, [(member, _, _)]) => member,
+        (None, _, _) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "DisplayDelegate requires an attribute marked with #[unprolix(display)]",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.#member, f)
+            }
+        }
+    })
+}
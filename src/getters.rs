@@ -0,0 +1,1079 @@
+use crate::case::method;
+use crate::collision;
+use crate::config::{
+    accessor_attrs, fields, key_names, members, ContainerConfig, FieldConfig, FieldFilter,
+    GetterKind,
+};
+use crate::ty::{
+    first_type_argument, has_segment, is_primitive, is_shared_ref, is_type, non_zero_value,
+    type_argument, type_arguments,
+};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse_quote, Block, Data, DeriveInput, Error, Expr, Field, GenericArgument, Ident, Item,
+    ItemFn, Member, Stmt, Type, Variant, Visibility,
+};
+
+pub(crate) fn expand(input: DeriveInput, filter: FieldFilter) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let vis = input.vis;
+
+    let container = ContainerConfig::from_attributes(&input.attrs)?;
+
+    // The attributes of a single variant enum are matched rather than accessed
+    let variant = match &input.data {
+        Data::Enum(e) => match e.variants.iter().collect::<Vec<_>>().as_slice() {
+            [variant] => {
+                // The container keys that apply only to the attributes of structs
+                let unsupported = ["arrays_as_slices", "qualified", "document_skipped"];
+
+                if let Some(k) = key_names(&input.attrs)?
+                    .into_iter()
+                    .find(|k| unsupported.iter().any(|u| k == u))
+                {
+                    return Err(Error::new_spanned(
+                        &k,
+                        format!("{} is not supported by the getters of enums", k),
+                    ));
+                }
+
+                Some((*variant).clone())
+            }
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "Getters is expected only for enums with a single variant",
+                ))
+            }
+        },
+        _ => None,
+    };
+
+    let fields = fields(input.data)?;
+
+    let inner = if container.transparent_ref {
+        let (member, field) = match fields.as_slice() {
+            [(member, field, _)] => (member, field),
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "transparent_ref is expected only for structs with a single attribute",
+                ))
+            }
+        };
+        let ty = &field.ty;
+        let mut f: ItemFn = parse_quote! {
+            /// Reference to the wrapped value
+            pub fn as_inner(&self) -> &#ty {
+                &self.#member
+            }
+        };
+
+        f.attrs.extend(accessor_attrs(&field.attrs));
+
+        Some(Stmt::Item(Item::Fn(f)))
+    } else {
+        None
+    };
+
+    // Note on the implementation listing the skipped attributes, along with their reasons
+    let lines: Vec<String> = if container.document_skipped {
+        fields
+            .iter()
+            .filter(|(_, _, config)| config.skip)
+            .map(|(member, _, config)| {
+                let member = match member {
+                    Member::Named(ident) => ident.to_string(),
+                    Member::Unnamed(i) => i.index.to_string(),
+                };
+
+                match &config.skip_reason {
+                    Some(reason) => format!("- `{}`: {}", member, reason.value()),
+                    None => format!("- `{}`", member),
+                }
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    let skipped = if lines.is_empty() {
+        quote! {}
+    } else {
+        let doc = format!("Skipped attributes:\n\n{}", lines.join("\n"));
+
+        quote! { #[doc = #doc] }
+    };
+
+    // Only one position of a tuple struct is accessed as its value
+    let mut primary = fields.iter().filter(|(_, _, config)| config.primary);
+
+    if let Some((Member::Named(_), field, _)) = primary.next() {
+        return Err(Error::new_spanned(
+            field,
+            "primary is expected only for tuple struct attributes",
+        ));
+    }
+
+    if let Some((_, field, _)) = primary.next() {
+        return Err(Error::new_spanned(
+            field,
+            "primary is expected on a single attribute",
+        ));
+    }
+
+    let mut block: Block = match variant {
+        Some(variant) => variant_getters(variant, filter, &container)?,
+        None => struct_getters(fields, filter, &container)?,
+    };
+
+    block.stmts.extend(inner);
+
+    if container.inline {
+        block.stmts.iter_mut().for_each(|s| {
+            if let Stmt::Item(Item::Fn(f)) = s {
+                f.attrs.push(parse_quote! { #[inline] });
+            }
+        });
+    }
+
+    let expanded = match container.trait_ {
+        Some(accessors) => {
+            let accessors: Ident = accessors.parse()?;
+            let methods: Vec<ItemFn> = block
+                .stmts
+                .into_iter()
+                .filter_map(|s| match s {
+                    Stmt::Item(Item::Fn(mut f)) => {
+                        f.vis = Visibility::Inherited;
+                        Some(f)
+                    }
+                    _ => None,
+                })
+                .collect();
+            let signatures: Vec<TokenStream> = methods
+                .iter()
+                .map(|f| {
+                    // Inlining applies to the implementation only
+                    let attrs = f.attrs.iter().filter(|a| !a.path.is_ident("inline"));
+                    let sig = &f.sig;
+
+                    quote! { #(#attrs)* #sig; }
+                })
+                .collect();
+
+            // Deprecation, documentation and `must_use` are declared by the trait, and doc
+            // aliases are not allowed on the implementation
+            let methods = methods.into_iter().map(|mut f| {
+                f.attrs.retain(|a| {
+                    !a.path.is_ident("deprecated")
+                        && !a.path.is_ident("doc")
+                        && !a.path.is_ident("must_use")
+                });
+                f
+            });
+
+            quote! {
+                #vis trait #accessors #generics #where_clause {
+                    #(#signatures)*
+                }
+
+                #skipped
+                impl #impl_generics #accessors #ty_generics for #name #ty_generics #where_clause {
+                    #(#methods)*
+                }
+            }
+        }
+        None => quote! {
+            #skipped
+            impl #impl_generics #name #ty_generics #where_clause #block
+        },
+    };
+
+    collision::check(&expanded)?;
+
+    Ok(expanded)
+}
+
+/// Getters of the attributes of a struct
+fn struct_getters(
+    fields: Vec<(Member, Field, FieldConfig)>,
+    filter: FieldFilter,
+    container: &ContainerConfig,
+) -> syn::Result<Block> {
+    // The load getter of an atomic attribute is generated with its setter, so it can't be
+    // requested alone
+    if let Some((_, field, _)) = fields
+        .iter()
+        .filter(|(_, field, config)| filter(field, config))
+        .find(|(_, _, c)| c.atomic && c.get && !c.set && !c.both)
+    {
+        return Err(Error::new_spanned(
+            field,
+            "get is not supported for atomic attributes, whose load getter is generated with the setter",
+        ));
+    }
+
+    fields
+        .into_iter()
+        .filter(|(_, field, config)| filter(field, config))
+        // The load getter of an atomic attribute is generated with its setter
+        .filter(|(_, _, config)| !config.atomic)
+        .filter(|(_, field, _)| match &container.only_type {
+            Some(only) => is_type(&field.ty, &only.value()),
+            None => true,
+        })
+        .try_fold(
+            syn::parse_str("{}").unwrap(),
+            |mut block: Block, (member, field, config)| {
+                if config.lock {
+                    block
+                        .stmts
+                        .extend(lock(&member, &field, &config, container)?);
+                } else {
+                    block
+                        .stmts
+                        .push(getter(&member, &field, &config, container)?);
+                }
+
+                if config.primary {
+                    let method = Ident::new("value", Span::call_site());
+                    let mut f = plain(GetterKind::Ref, &method, &member, &field.ty);
+
+                    f.attrs.extend(accessor_attrs(&field.attrs));
+                    block.stmts.push(Stmt::Item(Item::Fn(f)));
+                }
+
+                if config.with_ref {
+                    let case = container.rename_all.as_ref();
+                    let method = config.method(&member, case, |n| format!("{}_ref", n));
+                    let mut f = plain(GetterKind::Ref, &method, &member, &field.ty);
+
+                    f.attrs.extend(accessor_attrs(&field.attrs));
+                    block.stmts.push(Stmt::Item(Item::Fn(f)));
+                }
+
+                if !config.flatten.is_empty() {
+                    block
+                        .stmts
+                        .extend(flatten(&member, &field, &config, container)?);
+                }
+
+                if config.iter_mut {
+                    block
+                        .stmts
+                        .push(iter_mut(&member, &field, &config, container)?);
+                }
+
+                if config.lookup {
+                    block
+                        .stmts
+                        .extend(lookup(&member, &field, &config, container)?);
+                }
+
+                Ok::<_, Error>(block)
+            },
+        )
+}
+
+fn getter(
+    member: &Member,
+    field: &Field,
+    config: &FieldConfig,
+    container: &ContainerConfig,
+) -> syn::Result<Stmt> {
+    let method = config.method(member, container.rename_all.as_ref(), str::to_string);
+    // `ty = "Type"` is only inspected, the plain getters keep returning the attribute type
+    let ty = &config.inspected_ty(field)?;
+
+    let mut f: ItemFn;
+
+    if config.raw {
+        f = plain(GetterKind::Ref, &method, member, &field.ty);
+    } else if let Some(project) = &config.project {
+        let projection: TokenStream = project.parse()?;
+        let returns: Type = match &config.returns {
+            Some(returns) => returns.parse()?,
+            None => {
+                return Err(Error::new_spanned(
+                    project,
+                    "project requires the return type, provided with returns = \"Type\"",
+                ))
+            }
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> #returns {
+                self.#member.#projection
+            }
+        };
+    } else if let Some(returns) = &config.returns {
+        let returns: Type = returns.parse()?;
+
+        f = parse_quote! {
+            pub fn #method(&self) -> &(#returns) {
+                &self.#member as &(#returns)
+            }
+        };
+    } else if let Some(kind @ GetterKind::Clone) | Some(kind @ GetterKind::Copy) =
+        config.getter_kind
+    {
+        f = plain(kind, &method, member, &field.ty);
+    } else if config.as_slice {
+        let qualified = container.qualified && is_type(ty, "Vec");
+
+        // `SmallVec<[T; N]>` is parameterized by its inline array rather than the element
+        let ty = match (&config.elem, first_type_argument(ty)) {
+            (Some(elem), _) => elem.parse()?,
+            (None, Some(Type::Array(a))) => *a.elem,
+            (None, Some(ty)) => ty,
+            (None, None) if is_type(ty, "String") => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "as_slice is expected only for Vec types; did you mean to use #[unprolix(as_str)] for String?",
+                ))
+            }
+            (None, None) => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "as_slice is expected only for Vec-like types; the element type of other types can be provided with elem = \"T\"",
+                ))
+            }
+        };
+
+        let body: Expr = if qualified {
+            parse_quote!(::std::vec::Vec::as_slice(&self.#member))
+        } else {
+            parse_quote!(self.#member.as_slice())
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> &[#ty] {
+                #body
+            }
+        };
+    } else if config.opt_slice {
+        let ty = match type_argument(ty, "Option").and_then(|t| type_argument(&t, "Vec")) {
+            Some(ty) => ty,
+            None => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "opt_slice is expected only for Option<Vec<T>> types",
+                ))
+            }
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> ::core::option::Option<&[#ty]> {
+                self.#member.as_deref()
+            }
+        };
+    } else if config.dyn_ {
+        let ty = match type_argument(ty, "Box") {
+            Some(Type::TraitObject(t)) => t,
+            _ => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "dyn is expected only for Box<dyn Trait> types",
+                ))
+            }
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> &(#ty) {
+                self.#member.as_ref()
+            }
+        };
+    } else if config.get_primitive {
+        let value = match non_zero_value(ty) {
+            Some(value) => value,
+            None => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "get_primitive is expected only for NonZero types, such as NonZeroU32",
+                ))
+            }
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> #value {
+                self.#member.get()
+            }
+        };
+    } else if config.cell_get {
+        if let Some(ty) = type_argument(ty, "Cell") {
+            f = parse_quote! {
+                pub fn #method(&self) -> #ty {
+                    self.#member.get()
+                }
+            };
+        } else if let Some(ty) = type_argument(ty, "RefCell") {
+            f = parse_quote! {
+                #[must_use = "holding the guard keeps the lock/borrow"]
+                pub fn #method(&self) -> ::std::cell::Ref<'_, #ty> {
+                    self.#member.borrow()
+                }
+            };
+        } else {
+            return Err(Error::new_spanned(
+                ty,
+                "cell_get is expected only for Cell or RefCell types",
+            ));
+        }
+    } else if let Some(target) = &config.deref_as {
+        let target: Type = target.parse()?;
+
+        f = parse_quote! {
+            pub fn #method(&self) -> &#target {
+                &*self.#member
+            }
+        };
+    } else if config.once_get {
+        let ty = match ["OnceCell", "OnceLock"]
+            .iter()
+            .find_map(|w| type_argument(ty, w))
+        {
+            Some(ty) => ty,
+            None => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "once_get is expected only for OnceCell or OnceLock types",
+                ))
+            }
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> ::core::option::Option<&#ty> {
+                self.#member.get()
+            }
+        };
+    } else if config.deref_inner {
+        let ty = match ["Box", "Rc", "Arc"]
+            .iter()
+            .find_map(|w| type_argument(ty, w))
+        {
+            Some(ty) => ty,
+            None => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "deref_inner is expected only for Box, Rc or Arc types",
+                ))
+            }
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> &#ty {
+                &*self.#member
+            }
+        };
+    } else if config.as_str {
+        if is_type(ty, "String") {
+            let body: Expr = if container.qualified {
+                parse_quote!(::std::string::String::as_str(&self.#member))
+            } else {
+                parse_quote!(self.#member.as_str())
+            };
+
+            f = parse_quote! {
+                pub fn #method(&self) -> &str {
+                    #body
+                }
+            };
+        } else if type_argument(ty, "Box")
+            .or_else(|| type_argument(ty, "Cow"))
+            .map(|t| is_type(&t, "str"))
+            .unwrap_or(false)
+        {
+            f = parse_quote! {
+                pub fn #method(&self) -> &str {
+                    &self.#member
+                }
+            };
+        } else if is_type(ty, "Vec") {
+            return Err(Error::new_spanned(
+                ty,
+                "as_str is expected only for String, Box<str> or Cow<str> types; did you mean to use #[unprolix(as_slice)] for Vec?",
+            ));
+        } else {
+            return Err(Error::new_spanned(
+                ty,
+                "as_str is expected only for String, Box<str> or Cow<str> types",
+            ));
+        }
+    } else if config.upgrade {
+        let inner = match type_argument(ty, "Weak") {
+            Some(ty) => ty,
+            None => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "upgrade is expected only for Weak types",
+                ))
+            }
+        };
+        let strong: Type = if has_segment(ty, "rc") {
+            parse_quote!(::std::rc::Rc<#inner>)
+        } else {
+            parse_quote!(::std::sync::Arc<#inner>)
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> ::core::option::Option<#strong> {
+                self.#member.upgrade()
+            }
+        };
+    } else if config.ok_ref {
+        let types: Vec<Type> = type_arguments(ty, "Result")
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|a| match a {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .collect();
+
+        let (t, e) = match types.as_slice() {
+            [t, e] => (t, e),
+            _ => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "ok_ref is expected only for Result<T, E> types",
+                ))
+            }
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> ::core::result::Result<&#t, &#e> {
+                self.#member.as_ref()
+            }
+        };
+    } else if config.as_path {
+        if !is_type(ty, "PathBuf") {
+            return Err(Error::new_spanned(
+                ty,
+                "as_path is expected only for PathBuf types",
+            ));
+        }
+
+        let body: Expr = if container.qualified {
+            parse_quote!(::std::path::PathBuf::as_path(&self.#member))
+        } else {
+            parse_quote!(self.#member.as_path())
+        };
+
+        f = parse_quote! {
+            pub fn #method(&self) -> &::std::path::Path {
+                #body
+            }
+        };
+    } else if let (None, true, Type::Array(a)) =
+        (config.getter_kind, container.arrays_as_slices, ty)
+    {
+        let ty = &a.elem;
+
+        f = parse_quote! {
+            pub fn #method(&self) -> &[#ty] {
+                &self.#member
+            }
+        };
+    } else {
+        f = plain(
+            getter_kind(config, container, ty),
+            &method,
+            member,
+            &field.ty,
+        );
+    }
+
+    // The documentation search still finds a renamed getter by the attribute name
+    if let (Some(_), Member::Named(ident)) = (&config.rename, member) {
+        let alias = ident.to_string();
+
+        f.attrs.push(parse_quote!(#[doc(alias = #alias)]));
+    }
+
+    f.attrs.extend(accessor_attrs(&field.attrs));
+
+    Ok(Stmt::Item(Item::Fn(f)))
+}
+
+/// `get` and `contains` delegations of a `HashMap` or `HashSet` attribute
+fn lookup(
+    member: &Member,
+    field: &Field,
+    config: &FieldConfig,
+    container: &ContainerConfig,
+) -> syn::Result<Vec<Stmt>> {
+    let case = container.rename_all.as_ref();
+    let method_get = config.method(member, case, |n| format!("{}_get", n));
+    let method_contains = config.method(member, case, |n| format!("{}_contains", n));
+    // `ty = "Type"` is only inspected, the plain getters keep returning the attribute type
+    let ty = &config.inspected_ty(field)?;
+
+    let types: Vec<Type> = ["HashMap", "HashSet"]
+        .iter()
+        .find_map(|w| type_arguments(ty, w))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|a| match a {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+
+    let (get, contains): (ItemFn, ItemFn) = match types.as_slice() {
+        [k, v, ..] if is_type(ty, "HashMap") => (
+            parse_quote! {
+                pub fn #method_get(&self, k: &#k) -> ::core::option::Option<&#v> {
+                    self.#member.get(k)
+                }
+            },
+            parse_quote! {
+                pub fn #method_contains(&self, k: &#k) -> bool {
+                    self.#member.contains_key(k)
+                }
+            },
+        ),
+        [k, ..] if is_type(ty, "HashSet") => (
+            parse_quote! {
+                pub fn #method_get(&self, k: &#k) -> ::core::option::Option<&#k> {
+                    self.#member.get(k)
+                }
+            },
+            parse_quote! {
+                pub fn #method_contains(&self, k: &#k) -> bool {
+                    self.#member.contains(k)
+                }
+            },
+        ),
+        _ => {
+            return Err(Error::new_spanned(
+                ty,
+                "lookup is expected only for HashMap or HashSet types",
+            ))
+        }
+    };
+
+    Ok(vec![get, contains]
+        .into_iter()
+        .map(|mut f| {
+            f.attrs.extend(accessor_attrs(&field.attrs));
+            Stmt::Item(Item::Fn(f))
+        })
+        .collect())
+}
+
+/// Iterator over mutable references to the elements of a sequence, or the values of a map
+fn iter_mut(
+    member: &Member,
+    field: &Field,
+    config: &FieldConfig,
+    container: &ContainerConfig,
+) -> syn::Result<Stmt> {
+    let case = container.rename_all.as_ref();
+    let method = config.method(member, case, |n| format!("{}_iter_mut", n));
+    // `ty = "Type"` is only inspected, the plain getters keep returning the attribute type
+    let ty = &config.inspected_ty(field)?;
+
+    let types: Vec<Type> = ["Vec", "VecDeque", "HashMap", "BTreeMap"]
+        .iter()
+        .find_map(|w| type_arguments(ty, w))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|a| match a {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+
+    let (item, iter): (&Type, Ident) = match types.as_slice() {
+        [_, v, ..] if is_type(ty, "HashMap") || is_type(ty, "BTreeMap") => {
+            (v, parse_quote!(values_mut))
+        }
+        [t, ..] if is_type(ty, "Vec") || is_type(ty, "VecDeque") => (t, parse_quote!(iter_mut)),
+        _ => {
+            return Err(Error::new_spanned(
+                ty,
+                "iter_mut is expected only for Vec, VecDeque, HashMap or BTreeMap types",
+            ))
+        }
+    };
+
+    let mut f: ItemFn = parse_quote! {
+        pub fn #method(&mut self) -> impl ::core::iter::Iterator<Item = &mut #item> {
+            self.#member.#iter()
+        }
+    };
+
+    f.attrs.extend(accessor_attrs(&field.attrs));
+
+    Ok(Stmt::Item(Item::Fn(f)))
+}
+
+/// Getters delegating to the listed attributes of a nested struct, which the macro can't inspect
+fn flatten(
+    member: &Member,
+    field: &Field,
+    config: &FieldConfig,
+    container: &ContainerConfig,
+) -> syn::Result<Vec<Stmt>> {
+    config
+        .flatten
+        .iter()
+        .map(|(inner, ty)| {
+            let ty: Type =
+                match ty {
+                    Some(ty) => ty.parse()?,
+                    None => return Err(Error::new_spanned(
+                        inner,
+                        "flatten expects the type of every attribute, such as flatten(x = \"u32\")",
+                    )),
+                };
+            let method = method(container.rename_all.as_ref(), inner.to_string());
+            let mut f: ItemFn = parse_quote! {
+                pub fn #method(&self) -> &#ty {
+                    &self.#member.#inner
+                }
+            };
+
+            f.attrs.extend(accessor_attrs(&field.attrs));
+
+            Ok(Stmt::Item(Item::Fn(f)))
+        })
+        .collect()
+}
+
+/// Guard getters of a `Mutex` or `RwLock` attribute, panicking or not if the lock is poisoned
+fn lock(
+    member: &Member,
+    field: &Field,
+    config: &FieldConfig,
+    container: &ContainerConfig,
+) -> syn::Result<Vec<Stmt>> {
+    let case = container.rename_all.as_ref();
+    let method = config.method(member, case, str::to_string);
+    let method_try = config.method(member, case, |n| format!("try_{}", n));
+    // `ty = "Type"` is only inspected, the plain getters keep returning the attribute type
+    let ty = &config.inspected_ty(field)?;
+
+    let (guard, acquire): (Type, Ident) = if let Some(ty) = type_argument(ty, "Mutex") {
+        (
+            parse_quote!(::std::sync::MutexGuard<'_, #ty>),
+            parse_quote!(lock),
+        )
+    } else if let Some(ty) = type_argument(ty, "RwLock") {
+        (
+            parse_quote!(::std::sync::RwLockReadGuard<'_, #ty>),
+            parse_quote!(read),
+        )
+    } else {
+        return Err(Error::new_spanned(
+            ty,
+            "lock is expected only for Mutex or RwLock types",
+        ));
+    };
+
+    let get: ItemFn = parse_quote! {
+        /// # Panics
+        ///
+        /// Panics if the lock is poisoned
+        #[must_use = "holding the guard keeps the lock/borrow"]
+        pub fn #method(&self) -> #guard {
+            self.#member.#acquire().unwrap()
+        }
+    };
+    let try_get: ItemFn = parse_quote! {
+        pub fn #method_try(&self) -> ::std::sync::LockResult<#guard> {
+            self.#member.#acquire()
+        }
+    };
+
+    Ok(vec![get, try_get]
+        .into_iter()
+        .map(|mut f| {
+            f.attrs.extend(accessor_attrs(&field.attrs));
+            Stmt::Item(Item::Fn(f))
+        })
+        .collect())
+}
+
+/// Getters of the attributes of the single variant of an enum, returning them as `plain` does
+fn variant_getters(
+    variant: Variant,
+    filter: FieldFilter,
+    container: &ContainerConfig,
+) -> syn::Result<Block> {
+    let ident = &variant.ident;
+    let case = container.rename_all.as_ref();
+
+    // Only the attributes themselves are matched, so the getter modes other than `ref`, `clone`
+    // and `copy` are not available
+    let supported = [
+        "skip",
+        "get",
+        "set",
+        "both",
+        "rename",
+        "ref",
+        "clone",
+        "copy",
+        "test_only",
+    ];
+
+    for field in variant.fields.iter() {
+        if let Some(k) = key_names(&field.attrs)?
+            .into_iter()
+            .find(|k| !supported.iter().any(|s| k == s))
+        {
+            return Err(Error::new_spanned(
+                &k,
+                format!("{} is not supported by the getters of enums", k),
+            ));
+        }
+    }
+
+    members(variant.fields)?
+        .into_iter()
+        .filter(|(_, field, config)| filter(field, config))
+        .filter(|(_, field, _)| match &container.only_type {
+            Some(only) => is_type(&field.ty, &only.value()),
+            None => true,
+        })
+        .try_fold(
+            syn::parse_str("{}").unwrap(),
+            |mut block: Block, (member, field, config)| {
+                let method = config.method(&member, case, str::to_string);
+                let ty = &field.ty;
+
+                let mut f: ItemFn = match getter_kind(&config, container, ty) {
+                    GetterKind::Ref => parse_quote! {
+                        pub fn #method(&self) -> &#ty {
+                            let Self::#ident { #member: v, .. } = self;
+                            v
+                        }
+                    },
+                    GetterKind::Clone => parse_quote! {
+                        pub fn #method(&self) -> #ty {
+                            let Self::#ident { #member: v, .. } = self;
+                            v.clone()
+                        }
+                    },
+                    GetterKind::Copy => parse_quote! {
+                        pub fn #method(&self) -> #ty {
+                            let Self::#ident { #member: v, .. } = self;
+                            *v
+                        }
+                    },
+                };
+
+                f.attrs.extend(accessor_attrs(&field.attrs));
+                block.stmts.push(Stmt::Item(Item::Fn(f)));
+
+                Ok::<_, Error>(block)
+            },
+        )
+}
+
+/// How a getter without a dedicated mode returns the attribute
+fn getter_kind(config: &FieldConfig, container: &ContainerConfig, ty: &Type) -> GetterKind {
+    match config.getter_kind {
+        Some(kind) => kind,
+        None if container.copy && is_primitive(ty) => GetterKind::Copy,
+        // A shared reference is copied out, so it borrows for its own lifetime rather than the
+        // one of `&self`
+        None if is_shared_ref(ty) => GetterKind::Copy,
+        None => container.getter_kind.unwrap_or(GetterKind::Ref),
+    }
+}
+
+/// Getter returning the attribute type itself, or a reference to it
+fn plain(kind: GetterKind, method: &Ident, member: &Member, ty: &Type) -> ItemFn {
+    match kind {
+        GetterKind::Ref => parse_quote! {
+            pub fn #method(&self) -> &#ty {
+                &self.#member
+            }
+        },
+        GetterKind::Clone => parse_quote! {
+            pub fn #method(&self) -> #ty {
+                self.#member.clone()
+            }
+        },
+        GetterKind::Copy => parse_quote! {
+            pub fn #method(&self) -> #ty {
+                self.#member
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::accessor_field;
+
+    #[test]
+    fn enum_unsupported_keys() {
+        let e = expand(
+            parse_quote! {
+                enum Sealed {
+                    Inner {
+                        #[unprolix(as_slice)]
+                        a: Vec<u8>,
+                    },
+                }
+            },
+            accessor_field,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            "as_slice is not supported by the getters of enums",
+            e.to_string()
+        );
+
+        let e = expand(
+            parse_quote! {
+                #[unprolix(arrays_as_slices)]
+                enum Sealed {
+                    Inner { a: [u8; 2] },
+                }
+            },
+            accessor_field,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            "arrays_as_slices is not supported by the getters of enums",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn renamed_doc_alias() {
+        let expanded = expand(
+            parse_quote! {
+                struct Rect {
+                    #[unprolix(rename = "width")]
+                    w: u8,
+                    h: u8,
+                }
+            },
+            accessor_field,
+        )
+        .unwrap()
+        .to_string()
+        .replace(' ', "");
+
+        assert!(expanded.contains("#[doc(alias=\"w\")]pubfnwidth("));
+        assert!(!expanded.contains("alias=\"h\""));
+    }
+
+    #[test]
+    fn atomic_get_rejected() {
+        let e = expand(
+            parse_quote! {
+                struct Stats {
+                    #[unprolix(atomic, get)]
+                    requests: ::std::sync::atomic::AtomicUsize,
+                }
+            },
+            accessor_field,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            "get is not supported for atomic attributes, whose load getter is generated with the setter",
+            e.to_string()
+        );
+    }
+    #[test]
+    fn document_skipped() {
+        let expanded = expand(
+            parse_quote! {
+                #[unprolix(document_skipped)]
+                struct Credentials {
+                    user: String,
+                    #[unprolix(skip = "never exposed in logs")]
+                    password: String,
+                    #[unprolix(skip)]
+                    attempts: u8,
+                }
+            },
+            accessor_field,
+        )
+        .unwrap()
+        .to_string();
+        let doc = "Skipped attributes:\n\n- `password`: never exposed in logs\n- `attempts`";
+
+        assert!(expanded.contains(&quote!(#[doc = #doc] impl Credentials).to_string()));
+
+        let expanded = expand(
+            parse_quote! {
+                #[unprolix(document_skipped)]
+                struct Credentials {
+                    user: String,
+                }
+            },
+            accessor_field,
+        )
+        .unwrap()
+        .to_string();
+
+        assert!(!expanded.contains("doc"));
+    }
+    #[test]
+    fn slice_and_str_suggestions() {
+        let e = expand(
+            parse_quote! {
+                struct Note {
+                    #[unprolix(as_slice)]
+                    text: String,
+                }
+            },
+            accessor_field,
+        )
+        .err()
+        .unwrap();
+        assert!(e
+            .to_string()
+            .contains("did you mean to use #[unprolix(as_str)] for String?"));
+
+        let e = expand(
+            parse_quote! {
+                struct Note {
+                    #[unprolix(as_str)]
+                    bytes: Vec<u8>,
+                }
+            },
+            accessor_field,
+        )
+        .err()
+        .unwrap();
+        assert!(e
+            .to_string()
+            .contains("did you mean to use #[unprolix(as_slice)] for Vec?"));
+    }
+    #[test]
+    fn inspected_type_not_returned() {
+        let expanded = expand(
+            parse_quote! {
+                struct Packet {
+                    #[unprolix(ty = "Vec<u8>")]
+                    payload: Bytes,
+                    #[unprolix(clone, ty = "Vec<u8>")]
+                    header: Bytes,
+                }
+            },
+            accessor_field,
+        )
+        .unwrap()
+        .to_string()
+        .replace(' ', "");
+
+        assert!(expanded.contains("pubfnpayload(&self)->&Bytes{"));
+        assert!(expanded.contains("pubfnheader(&self)->Bytes{"));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::config::{cfg_attrs, fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let stmts: Vec<TokenStream> = fields(input.data)?
+        .into_iter()
+        .filter(|(_, _, config)| !config.skip)
+        .map(|(member, field, _)| {
+            let cfg = cfg_attrs(&field.attrs);
+
+            quote! {
+                #(#cfg)*
+                ::core::hash::Hash::hash(&self.#member, state);
+            }
+        })
+        .collect();
+
+    Ok(quote! {
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                #(#stmts)*
+            }
+        }
+    })
+}
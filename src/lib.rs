@@ -1,37 +1,27 @@
-#![feature(external_doc)]
-#![doc(include = "../README.md")]
+#![doc = include_str!("../README.md")]
 
 extern crate proc_macro;
 
+mod accessors;
+mod builder;
+mod case;
+mod collision;
+mod combined;
+mod config;
+mod constructor;
+mod display;
+mod getters;
+mod hash;
+mod methods;
+mod mut_getters;
+mod reset;
+mod setters;
+mod tuple;
+mod ty;
+
+use config::{accessor_field, annotated_field};
 use proc_macro::TokenStream;
-use proc_macro2::TokenTree;
-use quote::{quote, ToTokens};
-use syn::punctuated::Punctuated;
-use syn::{
-    parse_macro_input, parse_quote, Block, Data, DeriveInput, Expr, Field, FieldValue, Fields,
-    Ident, Member, Meta, NestedMeta, PathArguments, Stmt, Token, Type, Visibility,
-};
-
-fn search_for_attribute(f: &Field, attribute: &str) -> bool {
-    let mut attr = false;
-
-    for a in f.attrs.iter() {
-        match a.parse_meta().unwrap() {
-            Meta::List(l) => {
-                l.nested.iter().for_each(|l| match l {
-                    NestedMeta::Meta(m) => match m.to_token_stream().into_iter().next().unwrap() {
-                        TokenTree::Ident(i) if i == attribute => attr = true,
-                        _ => (),
-                    },
-                    _ => (),
-                });
-            }
-            _ => (),
-        }
-    }
-
-    attr
-}
+use syn::{parse_macro_input, DeriveInput};
 
 /// Generate a `pub fn new(...) -> Self` method
 ///
@@ -41,7 +31,272 @@ fn search_for_attribute(f: &Field, attribute: &str) -> bool {
 ///
 /// Some attributes who implements [`Default`] may not be required as parameter of the constructor.
 ///
-/// For that, there is the option to use `#[unprolix(default)]`
+/// For that, there is the option to use `#[unprolix(default)]`. The value is created with
+/// `<Type as Default>::default()`, qualified with the attribute type so it doesn't depend on
+/// inference.
+///
+/// Attributes marked with `#[unprolix(skip)]` are also not expected as arguments, and are filled
+/// by a `..Default::default()` tail instead. This requires the struct to implement [`Default`].
+///
+/// A custom expression can be provided with `#[unprolix(default = "expr")]`. The other arguments
+/// of `new` are in scope, so the value may be computed from them.
+///
+/// Types known not to implement [`Default`], such as references other than `&str` and `&[T]`,
+/// function pointers and raw pointers, must provide such an expression.
+///
+/// ```compile_fail
+/// use unprolix::Constructor;
+///
+/// #[derive(Constructor)]
+/// struct SomeStruct<'a> {
+///     #[unprolix(default)]
+///     a: &'a u8,
+/// }
+/// ```
+///
+/// A defaulted attribute whose type uses a type parameter of the struct bounds that type by
+/// [`Default`] in the `new` impl. So a defaulted `T` requires `T: Default`, but a defaulted
+/// `Vec<T>` doesn't bound `T`.
+///
+/// To still allow the default to be replaced, `#[unprolix(default, overridable)]` will expect the
+/// attribute as an `Option<T>` argument, using the default value for `None`.
+///
+/// With `#[unprolix(defaults_last)]` on the struct, the defaulted attributes are placed after the
+/// others in the struct literal of `new`, which only makes the expanded code easier to read.
+///
+/// With `#[unprolix(derive_default)]` on the struct, [`Default`] is also implemented. It
+/// delegates to `new` if every attribute is defaulted, and otherwise builds the struct with the
+/// default value of every argument of `new`.
+///
+/// ## Const
+///
+/// With `#[unprolix(const_fn)]` on the struct, `new` is generated as a `const fn`. Since
+/// `Default::default()` can't be called in const contexts, a defaulted primitive attribute is set
+/// to its zero value, such as `0` or `false`, and every other defaulted attribute must provide a
+/// const expression with `#[unprolix(default = "expr")]`.
+///
+/// ```compile_fail
+/// use unprolix::Constructor;
+///
+/// #[derive(Constructor)]
+/// #[unprolix(const_fn)]
+/// struct SomeStruct {
+///     #[unprolix(default)]
+///     a: Vec<u8>,
+/// }
+/// ```
+///
+/// With `#[unprolix(const_default)]` on the struct instead, `new` is a `const fn` as well, and an
+/// associated `pub const DEFAULT: Self` is created by `new` with the zero value of every argument.
+/// Every argument must then be a primitive.
+///
+/// ```
+/// use unprolix::Constructor;
+///
+/// #[derive(Constructor)]
+/// #[unprolix(const_default)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(default = "5")]
+///     b: u8,
+/// }
+///
+/// const S: SomeStruct = SomeStruct::DEFAULT;
+/// assert_eq!((0, 5), (S.a, S.b));
+/// ```
+///
+/// ## Singleton
+///
+/// With `#[unprolix(singleton)]` on the struct, an associated `pub const INSTANCE: Self` is
+/// generated as well. Every attribute must then be defaulted, with either a primitive type or a
+/// const expression provided with `#[unprolix(default = "expr")]`.
+///
+/// ```
+/// use unprolix::Constructor;
+///
+/// #[derive(Constructor)]
+/// #[unprolix(singleton)]
+/// struct Marker;
+///
+/// const MARKER: Marker = Marker::INSTANCE;
+/// ```
+///
+/// ## Slices
+///
+/// A `Vec<T>` attribute, where `T` implements [`Clone`], can be received as `&[T]` with
+/// `#[unprolix(from_slice)]`
+///
+/// A `Vec<T>` or `HashSet<T>` attribute can be received as `impl IntoIterator<Item = T>` with
+/// `#[unprolix(collect)]`, and is collected from it
+///
+/// ## Conversions
+///
+/// An attribute marked with `#[unprolix(into)]` is received as `impl Into<T>` and converted. With
+/// `#[unprolix(into)]` on the struct, every attribute received as is is converted instead.
+///
+/// With `#[unprolix(where_clause)]` on the struct as well, each converted argument is a named
+/// type parameter of `new` rather than an `impl Into<T>`, such as
+/// `pub fn new<__A: Into<String>, __B: Into<i32>>(a: __A, b: __B) -> Self`.
+///
+/// ## Borrowed arguments
+///
+/// With `#[unprolix(required_ref)]` on the struct, `new` receives the attributes that are not
+/// defaulted as `&T`, and clones them into the struct. Their types are expected to implement
+/// [`Clone`].
+///
+/// ## Parameter names
+///
+/// The parameter of an attribute is named after it, unless another name is provided with
+/// `#[unprolix(arg = "name")]`
+///
+/// ## Partial construction
+///
+/// With `#[unprolix(partial)]` on the struct, a `pub fn new_partial(...) -> PartialSomeStruct`
+/// receives only the attributes that are not defaulted. The partial struct has a chainable setter
+/// for each defaulted attribute, and `finish` builds the instance with the default value of the
+/// attributes that were not set.
+///
+/// ```
+/// use unprolix::Constructor;
+///
+/// #[derive(Constructor)]
+/// #[unprolix(partial)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(default = "5")]
+///     b: u8,
+/// }
+///
+/// let s = SomeStruct::new_partial(1).b(2).finish();
+/// assert_eq!(2, s.b);
+/// assert_eq!(5, SomeStruct::new_partial(1).finish().b);
+/// ```
+///
+/// ## Validation
+///
+/// With `#[unprolix(try_new, error = "Type")]` on the struct, an additional
+/// `pub fn try_new(...) -> Result<Self, Type>` receives the same arguments as `new`. Every
+/// attribute marked with `#[unprolix(validate = "path")]` is then checked by calling
+/// `path(&self.attribute)`, a function returning `Result<(), E>` where `Type` implements
+/// `From<E>`.
+///
+/// ```
+/// use unprolix::Constructor;
+///
+/// fn positive(v: &i32) -> Result<(), String> {
+///     if *v > 0 {
+///         Ok(())
+///     } else {
+///         Err(format!("{} is not positive", v))
+///     }
+/// }
+///
+/// #[derive(Constructor)]
+/// #[unprolix(try_new, error = "String")]
+/// struct SomeStruct {
+///     #[unprolix(validate = "positive")]
+///     a: i32,
+/// }
+///
+/// assert!(SomeStruct::try_new(1).is_ok());
+/// assert!(SomeStruct::try_new(-1).is_err());
+/// ```
+///
+/// With `#[unprolix(async)]` on the struct as well, `try_new` is an `async fn` and the validation
+/// functions are `async` too, awaited in turn.
+///
+/// With `#[unprolix(checked_unchecked)]` instead of `try_new`, the validated function is `new`
+/// itself, and the unvalidated one is `pub unsafe fn new_unchecked(...) -> Self`, mirroring
+/// `str::from_utf8` and `str::from_utf8_unchecked`. Calling it with arguments that don't pass
+/// the validation functions breaks the invariants the type relies on, hence the `unsafe`.
+///
+/// ```
+/// use unprolix::Constructor;
+///
+/// fn even(v: &u8) -> Result<(), String> {
+///     match v % 2 {
+///         0 => Ok(()),
+///         _ => Err(format!("{} is odd", v)),
+///     }
+/// }
+///
+/// #[derive(Constructor)]
+/// #[unprolix(checked_unchecked, error = "String")]
+/// struct SomeStruct {
+///     #[unprolix(validate = "even")]
+///     a: u8,
+/// }
+///
+/// assert!(SomeStruct::new(3).is_err());
+/// assert_eq!(3, unsafe { SomeStruct::new_unchecked(3) }.a);
+/// ```
+///
+/// With `#[unprolix(track_caller)]` on the struct, the validated function is annotated with
+/// `#[track_caller]`, unless it is `async`, so panics of the validation functions point to the
+/// caller.
+///
+/// With `#[unprolix(gen_error)]` instead of an error type, a `SomeStructError` enum is generated
+/// with a variant per validated attribute, named after it in `PascalCase`. The error of a
+/// validation function is wrapped by its variant if the attribute provides its type with
+/// `#[unprolix(error = "Type")]`, and discarded otherwise.
+///
+/// ```ignore
+/// #[derive(Constructor)]
+/// #[unprolix(try_new, gen_error)]
+/// struct SomeStruct {
+///     #[unprolix(validate = "positive", error = "String")]
+///     some_value: i32,
+///     #[unprolix(validate = "not_empty")]
+///     b: String,
+/// }
+///
+/// // Generates
+/// #[derive(Debug)]
+/// enum SomeStructError {
+///     SomeValue(String),
+///     B,
+/// }
+/// ```
+///
+/// ## Named arguments
+///
+/// Positional arguments are error-prone for structs with many attributes. With
+/// `#[unprolix(named_args)]` on the struct, a `SomeStructArgs` struct with the public parameters
+/// is generated, and `new` receives it instead.
+///
+/// ```ignore
+/// #[derive(Constructor)]
+/// #[unprolix(named_args)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(default)]
+///     b: u8,
+/// }
+///
+/// let s = SomeStruct::new(SomeStructArgs { a: 1 });
+/// ```
+///
+/// ## Conditional compilation
+///
+/// `#[cfg(...)]` attributes of a field are kept on its parameter and value, so the field is
+/// expected by `new` only when it is compiled.
+///
+/// ## Lints
+///
+/// An argument-free `new` is annotated with `#[allow(clippy::new_without_default)]`, unless the
+/// [`Default`] implementation is generated with `#[unprolix(derive_default)]`
+///
+/// ## Inlining
+///
+/// With `#[unprolix(inline)]` on the struct, `new` is annotated with `#[inline]`. The flag is
+/// shared with the getters and setters, whose methods are annotated as well.
+///
+/// ## Uninitialized
+///
+/// With the `unsafe-uninit` crate feature, attributes marked with `#[unprolix(uninit)]` are left
+/// uninitialized by an additional `pub unsafe fn new_uninit(...) -> Self`, which doesn't expect
+/// them as arguments. These attributes must be of a type that is valid while uninitialized, such
+/// as `MaybeUninit<T>` or `[MaybeUninit<T>; N]`.
 ///
 /// ## Expansion
 ///
@@ -65,7 +320,7 @@ fn search_for_attribute(f: &Field, attribute: &str) -> bool {
 ///         Self {
 ///             a,
 ///             b,
-///             c: Default::default(),
+///             c: <u8 as ::core::default::Default>::default(),
 ///         }
 ///     }
 /// }
@@ -74,281 +329,1053 @@ fn search_for_attribute(f: &Field, attribute: &str) -> bool {
 pub fn constructor(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let name = input.ident;
-    let data = input.data;
-
-    let mut values: Punctuated<FieldValue, Token![,]> = Punctuated::new();
-    let args: Punctuated<Field, Token![,]> = match data {
-        Data::Struct(syn::DataStruct {
-            struct_token: _,
-            fields: Fields::Named(f),
-            semi_token: _,
-        }) => f
-            .named
-            .into_pairs()
-            .filter_map(|mut p| {
-                let ident = p.value().ident.as_ref().cloned().unwrap();
-
-                let default = search_for_attribute(p.value(), "default");
-                if default {
-                    let fv = FieldValue {
-                        attrs: vec![],
-                        member: Member::Named(ident.clone()),
-                        colon_token: Some(<Token![:]>::default()),
-                        expr: Expr::Call(syn::parse_str("Default::default()").unwrap()),
-                    };
-                    values.push(fv);
-
-                    None
-                } else {
-                    let fv = FieldValue {
-                        attrs: vec![],
-                        member: Member::Named(ident.clone()),
-                        colon_token: None,
-                        expr: Expr::Verbatim(ident.to_token_stream()),
-                    };
-                    values.push(fv);
-
-                    (*p.value_mut()).attrs = vec![];
-                    (*p.value_mut()).vis = Visibility::Inherited;
-                    (*p.value_mut()).colon_token = None;
-
-                    Some(p)
-                }
-            })
-            .collect(),
-        _ => Punctuated::new(),
-    };
-
-    let expanded = quote! {
-        impl #name {
-            pub fn new(#args) -> #name {
-                #name {
-                    #values
-                }
-            }
-        }
-    };
-
-    TokenStream::from(expanded)
+    constructor::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
-/// Generate `pub fn attribute(&self) -> Type { &self.attribute }` functions for every non-public
-/// attribute.
-///
-/// ## Skip
-///
-/// To skip certain attributes that you don't want to expose, you can use `#[unprolix(skip)]`
+/// Generate a `SomeStructBuilder` struct, with a chainable setter for every attribute and a
+/// `pub fn build(self) -> Result<SomeStruct, &'static str>` method. `SomeStruct::builder()`
+/// returns an empty builder.
 ///
-/// ## Copy
+/// `build` fails with a message naming the first attribute that is not set.
 ///
-/// If your type implements [`Copy`] and references are more expensive than copying, then you can
-/// use `#[unprolix(copy)]`
+/// ## Default
 ///
-/// This is recommended for raw numbers or other simple types
+/// Attributes marked with `#[unprolix(default)]` or `#[unprolix(default = "expr")]` may be left
+/// unset, and are then set to their default value. The expression may refer to the attributes
+/// that are not defaulted.
 ///
-/// ## Slice
+/// ## Skip
 ///
-/// Its not a good practice to pass vectors as references. For that, or any type that implements an
-/// `T<S, ...> fn as_slice(&self) -> &[S]`, you can use `#[unprolix(as_slice)]`
+/// Attributes marked with `#[unprolix(skip)]` have no setter, and are set to
+/// `Default::default()`
 ///
 /// ## Expansion
 ///
 /// The following code
 ///
 /// ```ignore
-/// #[derive(Getters)]
+/// #[derive(Builder)]
 /// struct SomeStruct {
-///     a: HashMap<String, i32>,
-///     #[unprolix(copy)]
+///     a: u8,
+///     #[unprolix(default = "5")]
 ///     b: u8,
-///     #[unprolix(skip)]
-///     c: u8,
-///     #[unprolix(as_slice)]
-///     d: Vec<u8>,
 /// }
 /// ```
 ///
 /// Expands to
 ///
 /// ```ignore
-/// impl SomeStruct {
-///     pub fn a(&self) -> &HashMap<String, i32> {
-///         &self.a
+/// struct SomeStructBuilder {
+///     a: Option<u8>,
+///     b: Option<u8>,
+/// }
+///
+/// impl Default for SomeStructBuilder {
+///     fn default() -> Self {
+///         Self { a: None, b: None }
 ///     }
+/// }
 ///
-///     pub fn b(&self) -> u8 {
-///         self.b
+/// impl SomeStructBuilder {
+///     pub fn a(mut self, v: u8) -> Self {
+///         self.a = Some(v);
+///         self
 ///     }
 ///
-///     pub fn d(&self) -> &[u8] {
-///         self.b.as_slice()
+///     pub fn b(mut self, v: u8) -> Self {
+///         self.b = Some(v);
+///         self
+///     }
+///
+///     pub fn build(self) -> Result<SomeStruct, &'static str> {
+///         let a = self.a.ok_or("a is not set")?;
+///         let b = match self.b {
+///             Some(v) => v,
+///             None => 5,
+///         };
+///
+///         Ok(SomeStruct { a, b })
+///     }
+/// }
+///
+/// impl SomeStruct {
+///     pub fn builder() -> SomeStructBuilder {
+///         SomeStructBuilder::default()
 ///     }
 /// }
 /// ```
-#[proc_macro_derive(Getters, attributes(unprolix))]
-pub fn getters(input: TokenStream) -> TokenStream {
+#[proc_macro_derive(Builder, attributes(unprolix))]
+pub fn builder(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let name = input.ident;
-    let data = input.data;
-
-    let block: Block = match data {
-        Data::Struct(syn::DataStruct {
-            struct_token: _,
-            fields: Fields::Named(f),
-            semi_token: _,
-        }) => f
-            .named
-            .into_pairs()
-            .filter_map(|p| {
-                if let Visibility::Public(_) = p.value().vis {
-                    None
-                } else if search_for_attribute(p.value(), "skip") {
-                    None
-                } else {
-                    Some(p.into_value())
-                }
-            })
-            .fold(syn::parse_str("{}").unwrap(), |mut block, field| {
-                let copy = search_for_attribute(&field, "copy");
-                let as_slice = search_for_attribute(&field, "as_slice");
-
-                let ident = field.ident.as_ref().cloned().unwrap();
-                let ty = field.ty;
-
-                let f: Stmt;
-
-                if copy {
-                    f = parse_quote! {
-                        pub fn #ident(&self) -> #ty {
-                            self.#ident
-                        }
-                    };
-                } else if as_slice {
-                    let ty = match &ty {
-                        Type::Path(p) => {
-                            let v = p.path.segments.iter().next().unwrap().clone();
-                            let v = match v.arguments {
-                                PathArguments::AngleBracketed(v) => v,
-                                _ => panic!("Vector type expected"),
-                            };
-                            v.args.into_iter().next().unwrap()
-                        }
-                        _ => panic!("as_slice is expected only for Vec types"),
-                    };
-
-                    f = parse_quote! {
-                        pub fn #ident(&self) -> &[#ty] {
-                            self.#ident.as_slice()
-                        }
-                    };
-                } else {
-                    f = parse_quote! {
-                        pub fn #ident(&self) -> &#ty {
-                            &self.#ident
-                        }
-                    };
-                }
-
-                block.stmts.push(f);
-
-                block
-            }),
-        _ => syn::parse_str("{}").unwrap(),
-    };
-
-    let expanded = quote! {
-        impl #name #block
-    };
-
-    TokenStream::from(expanded)
+    builder::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
-/// Generate `pub fn attribute(&mut self, v: T) { self.attribute = v; }` functions for every non-public
+/// Generate the functions of [`Constructor`](derive.Constructor.html),
+/// [`Getters`](derive.Getters.html) and [`Setters`](derive.Setters.html) at once.
+///
+/// ## Parts
+///
+/// To generate only some of them, you can use `#[unprolix(constructor)]`, `#[unprolix(getters)]`
+/// and `#[unprolix(setters)]` on the struct. Without any of these keys, all three are generated.
+///
+/// ```ignore
+/// #[derive(Unprolix)]
+/// #[unprolix(constructor, getters)]
+/// struct SomeStruct {
+///     a: u8,
+/// }
+///
+/// let s = SomeStruct::new(1);
+/// assert_eq!(&1, s.a());
+/// ```
+#[proc_macro_derive(Unprolix, attributes(unprolix))]
+pub fn unprolix(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    combined::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate `pub fn attribute(&self) -> Type { &self.attribute }` functions for every non-public
 /// attribute.
 ///
 /// ## Skip
 ///
 /// To skip certain attributes that you don't want to expose, you can use `#[unprolix(skip)]`
 ///
-/// ## Expansion
+/// A reason may be recorded with `#[unprolix(skip = "reason")]`. It isn't visible unless the
+/// struct is marked with `#[unprolix(document_skipped)]`, which documents the implementation with
+/// the list of skipped attributes and their reasons.
 ///
-/// The following code
+/// With `#[unprolix(only_type = "Type")]` on the struct, only the attributes whose type is named
+/// `Type`, such as `String`, have getters. The name is compared to the last segment of the type
+/// path, without its generic arguments.
 ///
-/// ```ignore
-/// #[derive(Setters)]
+/// ## Tuple structs
+///
+/// The positional attributes of a tuple struct have getters named `field_0`, `field_1`, and so
+/// on, unless renamed with `#[unprolix(rename = "name")]`
+///
+/// A single position marked with `#[unprolix(primary)]` is also returned by
+/// `pub fn value(&self) -> &T`, besides its positional getter
+///
+/// ```compile_fail
+/// use unprolix::Getters;
+///
+/// #[derive(Getters)]
+/// struct Tagged(#[unprolix(primary)] u8, #[unprolix(primary)] u8);
+/// ```
+///
+/// ## Enums
+///
+/// The attributes of an enum with a single variant, such as a sealed type, have getters matching
+/// the variant. These getters return a reference, a copy or a clone of the attribute, as set with
+/// `ref`, `copy` or `clone`; the other getter modes, as well as `arrays_as_slices`, `qualified` and
+/// `document_skipped` on the enum, are an error. Enums with more than one variant are an error.
+///
+/// ```
+/// use unprolix::Getters;
+///
+/// #[derive(Getters)]
+/// enum Sealed {
+///     Inner { a: u8 },
+/// }
+///
+/// assert_eq!(&1, Sealed::Inner { a: 1 }.a());
+/// ```
+///
+/// ## Transparent newtypes
+///
+/// With `#[unprolix(transparent_ref)]` on a struct with a single attribute, such as a
+/// `#[repr(transparent)]` newtype, an additional `pub fn as_inner(&self) -> &Inner` returns the
+/// wrapped value. It is a plain reference to the attribute, so no `unsafe` code is involved; the
+/// layout guarantee of `#[repr(transparent)]` is only relevant for casts made elsewhere.
+///
+/// ## Copy
+///
+/// If your type implements [`Copy`] and references are more expensive than copying, then you can
+/// use `#[unprolix(copy)]`
+///
+/// This is recommended for raw numbers or other simple types
+///
+/// To also get a reference for uniform APIs, `#[unprolix(copy, with_ref)]` additionally generates
+/// `pub fn attribute_ref(&self) -> &T`
+///
+/// A shared reference attribute `&'a T` is copied out by default, returning `&'a T`, so the
+/// result may outlive the borrow of the struct. `#[unprolix(ref)]` returns `&&'a T` instead.
+///
+/// Similarly, `#[unprolix(clone)]` returns a clone of the attribute, and `#[unprolix(ref)]` a
+/// reference to it. The default for the attributes without any of these keys can be set with
+/// `#[unprolix(getter_kind = "ref" | "clone" | "copy")]` on the struct.
+///
+/// `#[unprolix(copy)]` on the struct copies only the primitive attributes, such as integers and
+/// `bool`, and any attribute marked with `copy`. `#[unprolix(ref)]` keeps a primitive attribute
+/// returned by reference.
+///
+/// A non-zero integer attribute, such as `NonZeroU32`, can return its primitive value, such as
+/// `u32`, via `get` with `#[unprolix(get_primitive)]`
+///
+/// ## Slice
+///
+/// Its not a good practice to pass vectors as references. For that, or any type that implements an
+/// `T<S, ...> fn as_slice(&self) -> &[S]`, you can use `#[unprolix(as_slice)]`. The element type
+/// of a type parameterized by an array, such as `SmallVec<[S; N]>`, is the one of the array. For
+/// other types, it can be provided with `#[unprolix(as_slice, elem = "S")]`.
+///
+/// With `#[unprolix(arrays_as_slices)]` on the struct, every `[T; N]` attribute returns `&[T]`,
+/// unless marked with `ref`, `clone` or `copy`
+///
+/// An attribute marked with `#[unprolix(raw)]` always returns `&T`, regardless of the keys of the
+/// struct, such as `arrays_as_slices` or `copy`
+///
+/// An `Option<Vec<T>>` attribute can return `Option<&[T]>` via `Option::as_deref` with
+/// `#[unprolix(opt_slice)]`
+///
+/// ## Str
+///
+/// A `String`, `Box<str>` or `Cow<'_, str>` attribute can return `&str` with
+/// `#[unprolix(as_str)]`
+///
+/// ## Type aliases
+///
+/// The keys above inspect the type of the attribute as written, so they don't see through type
+/// aliases. The aliased type can be provided with `#[unprolix(ty = "Type")]`, such as
+/// `#[unprolix(as_slice, ty = "Vec<u8>")]` for an attribute of type `Bytes = Vec<u8>`.
+///
+/// ## Trait objects
+///
+/// For `Box<dyn Trait>` attributes, you can use `#[unprolix(dyn)]` to return `&dyn Trait` instead
+/// of a reference to the box
+///
+/// ## Return type
+///
+/// `#[unprolix(returns = "dyn Trait")]` returns `&(dyn Trait)` instead of a reference to the
+/// attribute type, casting the attribute with `as`. An invalid cast is a compile error.
+///
+/// ```compile_fail
+/// use unprolix::Getters;
+///
+/// #[derive(Getters)]
 /// struct SomeStruct {
-///     a: u8,
-///     #[unprolix(skip)]
-///     b: u8,
+///     #[unprolix(returns = "dyn std::fmt::Display")]
+///     a: Vec<u8>,
 /// }
 /// ```
 ///
-/// Expands to
+/// An arbitrary projection of the attribute is returned with
+/// `#[unprolix(project = "method().chain()", returns = "Type")]`, generating
+/// `pub fn attribute(&self) -> Type { self.attribute.method().chain() }`. Since the type of the
+/// projection can't be inferred, `returns` is required and is the whole return type here, such as
+/// `&[u8]` or `usize`.
 ///
-/// ```ignore
-/// impl SomeStruct {
-///     pub fn a(&mut self, v: u8) {
-///         self.a = v;
-///     }
+/// ```
+/// use unprolix::Getters;
 ///
-///     pub fn a_as_mut(&mut self) -> &mut u8 {
-///         &mut self.a
-///     }
+/// #[derive(Getters)]
+/// struct SomeStruct {
+///     #[unprolix(project = "as_slice().len()", returns = "usize")]
+///     a: Vec<u8>,
 /// }
+///
+/// assert_eq!(2, SomeStruct { a: vec![1, 2] }.a());
 /// ```
-#[proc_macro_derive(Setters, attributes(unprolix))]
-pub fn setters(input: TokenStream) -> TokenStream {
+///
+/// ## Smart pointers
+///
+/// For `Box<T>`, `Rc<T>` and `Arc<T>` attributes, you can use `#[unprolix(deref_inner)]` to
+/// return `&T` instead of a reference to the pointer
+///
+/// For any other target, `#[unprolix(deref_as = "Target")]` returns `&Target` from the
+/// dereferenced attribute, relying on deref coercion
+///
+/// ## Lookup
+///
+/// A `HashMap<K, V>` attribute marked with `#[unprolix(lookup)]` additionally generates
+/// `pub fn attribute_get(&self, k: &K) -> Option<&V>` and
+/// `pub fn attribute_contains(&self, k: &K) -> bool`. A `HashSet<K>` attribute generates the same
+/// methods, with `attribute_get` returning `Option<&K>`.
+///
+/// ## Iteration
+///
+/// A `Vec<T>` or `VecDeque<T>` attribute marked with `#[unprolix(iter_mut)]` additionally generates
+/// `pub fn attribute_iter_mut(&mut self) -> impl Iterator<Item = &mut T>`. For `HashMap<K, V>` and
+/// `BTreeMap<K, V>` attributes, the iterator is over the values, as `&mut V`.
+///
+/// ## Flatten
+///
+/// The attributes of a nested struct can't be inspected by the macro, so getters delegating to
+/// them are generated for the ones listed with their type, such as
+/// `#[unprolix(flatten(x = "u32", y = "String"))]`. These generate `pub fn x(&self) -> &u32`
+/// returning `&self.attribute.x`, and so on.
+///
+/// ## Result
+///
+/// A `Result<T, E>` attribute can return `Result<&T, &E>` with `#[unprolix(ok_ref)]`
+///
+/// ## Path
+///
+/// A `PathBuf` attribute can return `&Path` with `#[unprolix(as_path)]`
+///
+/// ## Weak references
+///
+/// A `Weak<T>` attribute can return `Option<Arc<T>>` via `Weak::upgrade` with
+/// `#[unprolix(upgrade)]`. If the type is written with an `rc` path, such as `rc::Weak<T>`, the
+/// getter returns `Option<Rc<T>>` instead.
+///
+/// ## Interior mutability
+///
+/// With `#[unprolix(cell_get)]`, a `Cell<T>` attribute will return `T` via `Cell::get`, and a
+/// `RefCell<T>` attribute will return `Ref<'_, T>` via `RefCell::borrow`
+///
+/// A lazily initialized `OnceCell<T>` or `OnceLock<T>` attribute can return `Option<&T>` via
+/// `get` with `#[unprolix(once_get)]`
+///
+/// A `Mutex<T>` attribute marked with `#[unprolix(lock)]` returns `MutexGuard<'_, T>` via
+/// `Mutex::lock`, and a `RwLock<T>` attribute returns `RwLockReadGuard<'_, T>` via `RwLock::read`.
+/// These getters panic if the lock is poisoned, and an additional `try_attribute` getter returns
+/// the `LockResult` instead.
+///
+/// The `RefCell` and lock getters are `#[must_use]`, since a guard dropped right away is usually a
+/// mistake
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+///
+/// use std::cell::RefCell;
+/// use unprolix::Getters;
+///
+/// #[derive(Getters)]
+/// struct SomeStruct {
+///     #[unprolix(cell_get)]
+///     a: RefCell<u8>,
+/// }
+///
+/// SomeStruct { a: RefCell::new(1) }.a();
+/// ```
+///
+/// ## Qualified paths
+///
+/// With `#[unprolix(qualified)]` on the struct, the `as_slice` getters of `Vec` attributes and the
+/// `as_str` and `as_path` getters call the methods through their absolute `::std` paths, such as
+/// `::std::vec::Vec::as_slice(&self.attribute)`, so a method of a trait in scope can't be called
+/// instead
+///
+/// ## Rename
+///
+/// `#[unprolix(rename = "name")]` names the generated methods after `name` instead of the
+/// attribute. With `#[unprolix(rename_all = "camelCase")]` on the struct, every generated method
+/// name is converted to `"camelCase"`, `"PascalCase"`, `"SCREAMING_SNAKE_CASE"` or
+/// `"snake_case"`; a renamed attribute keeps its name as written.
+///
+/// A renamed getter carries `#[doc(alias = "attribute")]`, so the documentation search still finds
+/// it by the name of the attribute.
+///
+/// Generating the same method twice, such as by renaming two attributes identically, is a
+/// compile error naming the method.
+///
+/// ```compile_fail
+/// use unprolix::Getters;
+///
+/// #[derive(Getters)]
+/// struct SomeStruct {
+///     #[unprolix(rename = "value")]
+///     a: u8,
+///     #[unprolix(rename = "value")]
+///     b: u8,
+/// }
+/// ```
+///
+/// ## Trait
+///
+/// With `#[unprolix(trait = "SomeStructAccessors")]` on the struct, the getters are declared in a
+/// generated trait with the visibility of the struct, and implemented for it
+///
+/// ## Conditional compilation
+///
+/// The generated methods of an attribute keep its `#[cfg(...)]` attributes
+///
+/// The methods of an attribute marked with `#[unprolix(test_only)]` are compiled only for tests,
+/// under `#[cfg(test)]`
+///
+/// ## Inlining
+///
+/// With `#[unprolix(inline)]` on the struct, the generated methods are annotated with `#[inline]`
+///
+/// ## Deprecation
+///
+/// The generated methods of an attribute keep its `#[deprecated(...)]` attributes, so callers
+/// are warned
+///
+/// ```compile_fail
+/// #![deny(deprecated)]
+/// use unprolix::Getters;
+///
+/// #[derive(Getters)]
+/// struct SomeStruct {
+///     #[deprecated(note = "use b instead")]
+///     a: u8,
+///     b: u8,
+/// }
+///
+/// fn a(s: &SomeStruct) -> u8 {
+///     *s.a()
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// The type of an attribute is inspected only when a key requires it. A key used with an
+/// unexpected type, such as `as_slice` on an associated type, is reported as a compile error on
+/// the attribute type.
+///
+/// ```compile_fail
+/// use unprolix::Getters;
+///
+/// trait Storage {
+///     type Item;
+/// }
+///
+/// #[derive(Getters)]
+/// struct SomeStruct<S: Storage> {
+///     #[unprolix(as_slice)]
+///     a: <S as Storage>::Item,
+/// }
+/// ```
+///
+/// When another key fits the type, the error suggests it, such as `as_str` for a `String` marked
+/// with `as_slice`.
+///
+/// ```compile_fail
+/// use unprolix::Getters;
+///
+/// #[derive(Getters)]
+/// struct SomeStruct {
+///     #[unprolix(as_slice)]
+///     a: String,
+/// }
+/// ```
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(Getters)]
+/// struct SomeStruct {
+///     a: HashMap<String, i32>,
+///     #[unprolix(copy)]
+///     b: u8,
+///     #[unprolix(skip)]
+///     c: u8,
+///     #[unprolix(as_slice)]
+///     d: Vec<u8>,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl SomeStruct {
+///     pub fn a(&self) -> &HashMap<String, i32> {
+///         &self.a
+///     }
+///
+///     pub fn b(&self) -> u8 {
+///         self.b
+///     }
+///
+///     pub fn d(&self) -> &[u8] {
+///         self.b.as_slice()
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Getters, attributes(unprolix))]
+pub fn getters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    getters::expand(input, accessor_field)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate the same functions as [`Getters`](derive.Getters.html), but only for the attributes
+/// marked with `#[unprolix(get)]`.
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(PartialGetters)]
+/// struct SomeStruct {
+///     #[unprolix(get)]
+///     a: u8,
+///     #[unprolix(get, copy)]
+///     b: u8,
+///     c: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl SomeStruct {
+///     pub fn a(&self) -> &u8 {
+///         &self.a
+///     }
+///
+///     pub fn b(&self) -> u8 {
+///         self.b
+///     }
+/// }
+/// ```
+#[proc_macro_derive(PartialGetters, attributes(unprolix))]
+pub fn partial_getters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    getters::expand(input, annotated_field)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate `pub fn attribute_mut(&mut self) -> &mut T { &mut self.attribute }` functions for every
+/// non-public attribute.
+///
+/// Unlike [`Setters`](derive.Setters.html), no assignment methods are generated; only mutable
+/// references are exposed.
+///
+/// ## Skip
+///
+/// To skip certain attributes that you don't want to expose, you can use `#[unprolix(skip)]`
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(MutGetters)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(skip)]
+///     b: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl SomeStruct {
+///     pub fn a_mut(&mut self) -> &mut u8 {
+///         &mut self.a
+///     }
+/// }
+/// ```
+#[proc_macro_derive(MutGetters, attributes(unprolix))]
+pub fn mut_getters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    mut_getters::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate `pub fn attribute(&mut self, v: T) { self.attribute = v; }` functions for every non-public
+/// attribute.
+///
+/// ## Skip
+///
+/// To skip certain attributes that you don't want to expose, you can use `#[unprolix(skip)]`
+///
+/// ## Tuple structs
+///
+/// The positional attributes of a tuple struct are named `field_0`, `field_1`, and so on, such as
+/// `set_field_0` and `field_0_as_mut`, unless renamed with `#[unprolix(rename = "name")]`
+///
+/// ## Paths
+///
+/// A `PathBuf` attribute marked with `#[unprolix(as_ref)]` is set from `impl AsRef<Path>`, such
+/// as a `&str`, by `pub fn set_attribute(&mut self, v: impl AsRef<Path>)`
+///
+/// ## Modify
+///
+/// To update an attribute in place through a closure, you can use `#[unprolix(modify)]` to
+/// generate `pub fn modify_attribute(&mut self, f: impl FnOnce(&mut T))`
+///
+/// ## Replace
+///
+/// To replace an attribute and get its previous value back, you can use
+/// `#[unprolix(replace_with)]` to generate
+/// `pub fn take_and_replace_attribute(&mut self, v: T) -> T` and
+/// `pub fn replace_attribute_with(&mut self, f: impl FnOnce(&T) -> T) -> T`, which computes the
+/// new value from the current one
+///
+/// ## Lazy initialization
+///
+/// For an `Option<T>` attribute, `#[unprolix(get_or_insert)]` generates
+/// `pub fn attribute_or_insert_with(&mut self, f: impl FnOnce() -> T) -> &mut T`, which
+/// initializes the attribute with `f` only if it is `None`
+///
+/// ## Swap
+///
+/// To exchange an attribute with another instance, such as a scratch buffer, you can use
+/// `#[unprolix(swap)]` to generate `pub fn swap_attribute(&mut self, other: &mut Self)`
+///
+/// ## Clear
+///
+/// `#[unprolix(clearable)]` generates `pub fn clear_attribute(&mut self)`, setting the attribute
+/// to `Default::default()`, or to the expression of `#[unprolix(default = "expr")]` if any.
+/// Unlike in [`Constructor`](derive.Constructor.html), the other attributes are not in scope, so
+/// the expression can't be computed from them.
+///
+/// ## Atomics
+///
+/// An atomic attribute, such as `AtomicUsize`, marked with `#[unprolix(atomic)]` generates
+/// `pub fn set_attribute(&self, v: usize, order: Ordering)` and
+/// `pub fn attribute(&self, order: Ordering) -> usize` instead, since atomics are mutated through
+/// shared references. [`Getters`](derive.Getters.html) doesn't generate a getter for it, and
+/// marking it with `get` is an error, since the `get` restriction of
+/// [`Accessors`](derive.Accessors.html) would leave it without any method.
+///
+/// ## Copy from
+///
+/// With `#[unprolix(copy_from)]` on the struct, `pub fn set_all(&mut self, other: &Self)` sets
+/// every attribute that has setters to a clone of the attribute of `other`, so these attributes
+/// must implement [`Clone`]. Skipped and atomic attributes are kept.
+///
+/// ## Patch
+///
+/// With `#[unprolix(patch)]` on the struct, a `SomeStructPatch` struct is generated with an
+/// `Option` of every attribute that has setters, along with
+/// `pub fn apply_patch(&mut self, patch: SomeStructPatch)` setting the attributes that are
+/// `Some`. The patch implements [`Default`] with every attribute set to `None`.
+///
+/// ```
+/// use unprolix::Setters;
+///
+/// #[derive(Setters)]
+/// #[unprolix(patch)]
+/// struct SomeStruct {
+///     a: u8,
+///     b: u8,
+/// }
+///
+/// let mut s = SomeStruct { a: 1, b: 2 };
+/// s.apply_patch(SomeStructPatch {
+///     b: Some(3),
+///     ..Default::default()
+/// });
+///
+/// assert_eq!((1, 3), (s.a, s.b));
+/// ```
+///
+/// ## Reflection
+///
+/// With `#[unprolix(reflect)]` on the struct, a `SomeStructField` enum is generated with a variant
+/// per attribute that has setters, named after it in `PascalCase`, along with
+/// `pub fn set(&mut self, field: SomeStructField, value: Box<dyn Any>) -> Result<(), Box<dyn Any>>`.
+/// The value is downcast to the type of the attribute, and returned back if it is of another type.
+/// For generic structs, `set` is only available when the attribute types are `'static`.
+///
+/// ## Rename
+///
+/// `#[unprolix(rename = "name")]` names the generated methods after `name` instead of the
+/// attribute. With `#[unprolix(rename_all = "camelCase")]` on the struct, every generated method
+/// name is converted to `"camelCase"`, `"PascalCase"`, `"SCREAMING_SNAKE_CASE"` or
+/// `"snake_case"`; a renamed attribute keeps its name as written.
+///
+/// ## Conditional compilation
+///
+/// The generated methods of an attribute keep its `#[cfg(...)]` and `#[deprecated(...)]`
+/// attributes
+///
+/// As for the getters, `#[unprolix(test_only)]` restricts the methods of an attribute to tests
+///
+/// ## Track caller
+///
+/// With `#[unprolix(track_caller)]` on the struct, the generated methods are annotated with
+/// `#[track_caller]`
+///
+/// ## Inlining
+///
+/// With `#[unprolix(inline)]` on the struct, the generated methods are annotated with `#[inline]`
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(Setters)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(skip)]
+///     b: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl SomeStruct {
+///     pub fn a(&mut self, v: u8) {
+///         self.a = v;
+///     }
+///
+///     pub fn a_as_mut(&mut self) -> &mut u8 {
+///         &mut self.a
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Setters, attributes(unprolix))]
+pub fn setters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    setters::expand(input, accessor_field)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate the functions of both [`Getters`](derive.Getters.html) and
+/// [`Setters`](derive.Setters.html) for every non-public attribute.
+///
+/// ## Get and set
+///
+/// An attribute marked with `#[unprolix(get)]` only has getters, and an attribute marked with
+/// `#[unprolix(set)]` only has setters. `#[unprolix(both)]` is the default.
+///
+/// ## Skip
+///
+/// To skip certain attributes that you don't want to expose, you can use `#[unprolix(skip)]`
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(Accessors)]
+/// struct SomeStruct {
+///     #[unprolix(get)]
+///     a: u8,
+///     #[unprolix(set)]
+///     b: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl SomeStruct {
+///     pub fn a(&self) -> &u8 {
+///         &self.a
+///     }
+/// }
+///
+/// impl SomeStruct {
+///     pub fn set_b(&mut self, v: u8) {
+///         self.b = v;
+///     }
+///
+///     pub fn b_as_mut(&mut self) -> &mut u8 {
+///         &mut self.b
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Accessors, attributes(unprolix))]
+pub fn accessors(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    accessors::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Register the struct for [`unprolix_methods`](attr.unprolix_methods.html).
+///
+/// This generates no methods on its own. The struct is handed to `#[unprolix_methods]`, which
+/// injects the methods of [`Accessors`](derive.Accessors.html) into an impl of the struct.
+#[proc_macro_derive(UnprolixMethods, attributes(unprolix))]
+pub fn unprolix_methods_registry(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    methods::expand_registry(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Inject the functions of [`Accessors`](derive.Accessors.html) into an existing impl block.
+///
+/// A macro on an impl can't see the definition of the struct, so it must derive
+/// [`UnprolixMethods`](derive.UnprolixMethods.html) and be named with
+/// `#[unprolix_methods(for = SomeStruct)]`. The struct must be in scope: in the same module, it
+/// has to be defined before the impl, and from a child module, it is named with its path such as
+/// `super::SomeStruct`. The impl can't be anywhere else, since the private attributes wouldn't be
+/// visible. It must also use the same generic parameter names as the struct.
+///
+/// The keys of [`Accessors`](derive.Accessors.html) apply, and the generated traits and impls
+/// other than the inherent ones are emitted next to the impl.
+///
+/// ```
+/// use unprolix::{unprolix_methods, UnprolixMethods};
+///
+/// #[derive(UnprolixMethods)]
+/// struct SomeStruct {
+///     a: u8,
+/// }
+///
+/// #[unprolix_methods(for = SomeStruct)]
+/// impl SomeStruct {
+///     pub fn double(&self) -> u8 {
+///         self.a() * 2
+///     }
+/// }
+///
+/// let mut s = SomeStruct { a: 1 };
+///
+/// s.set_a(2);
+/// assert_eq!(4, s.double());
+/// ```
+#[proc_macro_attribute]
+pub fn unprolix_methods(args: TokenStream, item: TokenStream) -> TokenStream {
+    methods::expand_attribute(args.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __unprolix_inject(input: TokenStream) -> TokenStream {
+    methods::expand_inject(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate a conversion of the struct into a tuple of all its attributes, in declaration order.
+///
+/// The tuple of a struct with type parameters in its attributes is a foreign type that can't
+/// implement `From` for the struct, so these structs are not supported.
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(IntoTuple)]
+/// struct SomeStruct {
+///     a: u8,
+///     b: String,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl From<SomeStruct> for (u8, String) {
+///     fn from(v: SomeStruct) -> Self {
+///         (v.a, v.b)
+///     }
+/// }
+/// ```
+#[proc_macro_derive(IntoTuple, attributes(unprolix))]
+pub fn into_tuple(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    tuple::expand_into(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate a conversion of a tuple of the attributes, in declaration order, into the struct.
+///
+/// ## Default
+///
+/// Attributes marked with `#[unprolix(default)]` or `#[unprolix(default = "expr")]` are not part
+/// of the tuple and are set to their default value instead. As in
+/// [`Constructor`](derive.Constructor.html), the expression may refer to the other attributes.
+/// Attributes marked with `#[unprolix(skip)]` are also not part of the tuple, and are set to
+/// `Default::default()`.
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(FromTuple)]
+/// struct SomeStruct {
+///     a: u8,
+///     b: String,
+///     #[unprolix(default)]
+///     c: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl From<(u8, String)> for SomeStruct {
+///     fn from(t: (u8, String)) -> Self {
+///         let (a, b) = t;
+///
+///         Self {
+///             a: a,
+///             b: b,
+///             c: <u8 as ::core::default::Default>::default(),
+///         }
+///     }
+/// }
+/// ```
+#[proc_macro_derive(FromTuple, attributes(unprolix))]
+pub fn from_tuple(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    tuple::expand_from(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generate a `pub fn reset(&mut self)` method that sets every attribute to its default value.
+///
+/// This is useful for object pools, where instances are recycled instead of dropped.
+///
+/// ## Skip
+///
+/// To keep the current value of certain attributes, you can use `#[unprolix(skip)]`
+///
+/// ## Default
+///
+/// A custom reset value can be provided with `#[unprolix(default = "expr")]`. Unlike in
+/// [`Constructor`](derive.Constructor.html), the other attributes are not in scope, so the
+/// expression can't be computed from them.
+///
+/// ```compile_fail
+/// use unprolix::Reset;
+///
+/// #[derive(Reset)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(default = "a * 2")]
+///     b: u8,
+/// }
+/// ```
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(Reset)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(default = "5")]
+///     b: u8,
+///     #[unprolix(skip)]
+///     c: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl SomeStruct {
+///     pub fn reset(&mut self) {
+///         self.a = <u8 as ::core::default::Default>::default();
+///         self.b = 5;
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Reset, attributes(unprolix))]
+pub fn reset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    reset::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Implement [`Display`](std::fmt::Display) by delegating to a single attribute
+///
+/// The attribute is marked with `#[unprolix(display)]`. For structs with a single attribute, such
+/// as newtypes, the mark may be omitted.
+///
+/// ```compile_fail
+/// use unprolix::DisplayDelegate;
+///
+/// #[derive(DisplayDelegate)]
+/// struct SomeStruct {
+///     #[unprolix(display)]
+///     a: String,
+///     #[unprolix(display)]
+///     b: String,
+/// }
+/// ```
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(DisplayDelegate)]
+/// struct SomeStruct {
+///     #[unprolix(display)]
+///     a: String,
+///     b: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl Display for SomeStruct {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+///         Display::fmt(&self.a, f)
+///     }
+/// }
+/// ```
+#[proc_macro_derive(DisplayDelegate, attributes(unprolix))]
+pub fn display_delegate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    display::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Implement [`Hash`](std::hash::Hash) from the attributes not marked with `#[unprolix(skip)]`
+///
+/// This is useful when an attribute, such as a cache, shouldn't participate in the hash. To stay
+/// consistent with [`Eq`], the skipped attributes should also be ignored by the equality.
+///
+/// ## Expansion
+///
+/// The following code
+///
+/// ```ignore
+/// #[derive(Hashable)]
+/// struct SomeStruct {
+///     a: u8,
+///     #[unprolix(skip)]
+///     b: u8,
+/// }
+/// ```
+///
+/// Expands to
+///
+/// ```ignore
+/// impl Hash for SomeStruct {
+///     fn hash<H: Hasher>(&self, state: &mut H) {
+///         Hash::hash(&self.a, state);
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Hashable, attributes(unprolix))]
+pub fn hashable(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let name = input.ident;
-    let data = input.data;
-
-    let block: Block = match data {
-        Data::Struct(syn::DataStruct {
-            struct_token: _,
-            fields: Fields::Named(f),
-            semi_token: _,
-        }) => f
-            .named
-            .into_pairs()
-            .filter_map(|p| {
-                if let Visibility::Public(_) = p.value().vis {
-                    None
-                } else if search_for_attribute(p.value(), "skip") {
-                    None
-                } else {
-                    Some(p.into_value())
-                }
-            })
-            .fold(syn::parse_str("{}").unwrap(), |mut block, field| {
-                let ident = field.ident.as_ref().cloned().unwrap();
-                let method: Ident = syn::parse_str(format!("set_{}", ident).as_str()).unwrap();
-                let method_as_mut: Ident =
-                    syn::parse_str(format!("{}_as_mut", ident).as_str()).unwrap();
-                let ty = field.ty;
-
-                block.stmts.push(parse_quote! {
-                    pub fn #method(&mut self, v: #ty) {
-                        self.#ident = v;
-                    }
-                });
-
-                block.stmts.push(parse_quote! {
-                    pub fn #method_as_mut(&mut self) -> &mut #ty {
-                        &mut self.#ident
-                    }
-                });
-
-                block
-            }),
-        _ => syn::parse_str("{}").unwrap(),
-    };
-
-    let expanded = quote! {
-        impl #name #block
-    };
-
-    TokenStream::from(expanded)
+    hash::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
@@ -0,0 +1,106 @@
+use crate::accessors;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, DeriveInput, Error, File, Item, ItemImpl, Path, Token};
+
+/// Name of the macro that passes the tokens of the struct `name` to a callback
+fn registry(name: &syn::Ident) -> syn::Ident {
+    format_ident!("__unprolix_methods_{}", name)
+}
+
+/// Arguments of `#[unprolix_methods(for = SomeStruct)]`
+struct Target {
+    path: Path,
+}
+
+impl Parse for Target {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let error = |span| Error::new(span, "expected `for = SomeStruct`");
+
+        input.parse::<Token![for]>().map_err(|e| error(e.span()))?;
+        input.parse::<Token![=]>().map_err(|e| error(e.span()))?;
+        let path = input.parse().map_err(|e| error(e.span()))?;
+
+        if !input.is_empty() {
+            return Err(error(input.span()));
+        }
+
+        Ok(Self { path })
+    }
+}
+
+/// The user impl, in braces, followed by the struct it is for
+struct Injection {
+    imp: ItemImpl,
+    input: DeriveInput,
+}
+
+impl Parse for Injection {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in input);
+
+        Ok(Self {
+            imp: content.parse()?,
+            input: input.parse()?,
+        })
+    }
+}
+
+pub(crate) fn expand_registry(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = registry(&input.ident);
+
+    // The struct is re-emitted as a macro, so `#[unprolix_methods]` can read its attributes
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #name {
+            ({ $($callback:tt)* } { $($imp:tt)* }) => {
+                $($callback)*! { { $($imp)* } #input }
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #name;
+    })
+}
+
+pub(crate) fn expand_attribute(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let Target { mut path } = syn::parse2(args)?;
+    let imp: ItemImpl = syn::parse2(item)?;
+
+    if let Some((_, trait_, _)) = &imp.trait_ {
+        return Err(Error::new_spanned(
+            trait_,
+            "unprolix_methods expects an inherent impl of the struct",
+        ));
+    }
+
+    // The registry of the struct is next to it, so it can be reached with the same path
+    let last = path.segments.last_mut().unwrap();
+    last.ident = registry(&last.ident);
+    last.arguments = syn::PathArguments::None;
+
+    Ok(quote! {
+        #path! { { ::unprolix::__unprolix_inject } { #imp } }
+    })
+}
+
+pub(crate) fn expand_inject(tokens: TokenStream) -> syn::Result<TokenStream> {
+    let Injection { mut imp, input } = syn::parse2(tokens)?;
+    let file: File = syn::parse2(accessors::expand(input)?)?;
+    let mut items = vec![];
+
+    for item in file.items {
+        match item {
+            Item::Impl(i) if i.trait_.is_none() => imp.items.extend(i.items),
+            item => items.push(item),
+        }
+    }
+
+    Ok(quote! {
+        #imp
+        #(#items)*
+    })
+}
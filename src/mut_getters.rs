@@ -0,0 +1,42 @@
+use crate::collision;
+use crate::config::{accessor_attrs, accessor_field, fields, ContainerConfig};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Block, DeriveInput};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let container = ContainerConfig::from_attributes(&input.attrs)?;
+    let case = container.rename_all.as_ref();
+
+    let block: Block = fields(input.data)?
+        .into_iter()
+        .filter(|(_, field, config)| accessor_field(field, config))
+        .fold(
+            syn::parse_str("{}").unwrap(),
+            |mut block, (member, field, config)| {
+                let method = config.method(&member, case, |n| format!("{}_mut", n));
+                let ty = &field.ty;
+                let attrs = accessor_attrs(&field.attrs);
+
+                block.stmts.push(parse_quote! {
+                    #(#attrs)*
+                    pub fn #method(&mut self) -> &mut #ty {
+                        &mut self.#member
+                    }
+                });
+
+                block
+            },
+        );
+
+    let expanded = quote! {
+        impl #impl_generics #name #ty_generics #where_clause #block
+    };
+
+    collision::check(&expanded)?;
+
+    Ok(expanded)
+}
@@ -0,0 +1,39 @@
+use crate::config::{cfg_attrs, fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Block, DeriveInput, Error};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let block: Block = fields(input.data)?
+        .into_iter()
+        .filter(|(_, _, config)| !config.skip)
+        .try_fold(
+            syn::parse_str("{}").unwrap(),
+            |mut block: Block, (member, field, config)| {
+                let expr = config.default_expr(&field.ty)?;
+                let cfg = cfg_attrs(&field.attrs);
+
+                // Attributes on expression statements are unstable, but not on blocks
+                block.stmts.push(parse_quote! {
+                    #(#cfg)*
+                    {
+                        self.#member = #expr;
+                    }
+                });
+
+                Ok::<_, Error>(block)
+            },
+        )?;
+
+    let stmts = block.stmts;
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn reset(&mut self) {
+                #(#stmts)*
+            }
+        }
+    })
+}
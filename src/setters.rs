@@ -0,0 +1,387 @@
+use crate::case::Case;
+use crate::collision;
+use crate::config::{accessor_attrs, cfg_attrs, fields, ContainerConfig, FieldFilter};
+use crate::ty::{atomic_value, is_type, type_argument, used_generics};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Block, DeriveInput, Error, Ident, Item, Member, Stmt};
+
+pub(crate) fn expand(input: DeriveInput, filter: FieldFilter) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let vis = input.vis;
+
+    let container = ContainerConfig::from_attributes(&input.attrs)?;
+    let case = container.rename_all.as_ref();
+
+    let track_caller = if container.track_caller {
+        quote! { #[track_caller] }
+    } else {
+        quote! {}
+    };
+
+    // Attributes of the patch struct and the reflection enum, with their member in the struct
+    let mut assigned = vec![];
+
+    let mut block: Block = fields(input.data)?
+        .into_iter()
+        .filter(|(_, field, config)| filter(field, config))
+        .try_fold(
+            syn::parse_str("{}").unwrap(),
+            |mut block: Block, (member, field, config)| {
+                let method = config.method(&member, case, |n| format!("set_{}", n));
+                let method_as_mut = config.method(&member, case, |n| format!("{}_as_mut", n));
+                let ty = &field.ty;
+                let attrs = accessor_attrs(&field.attrs);
+
+                // Atomics are both stored and loaded through a shared reference
+                if config.atomic {
+                    let value = match atomic_value(ty) {
+                        Some(value) => value,
+                        None => {
+                            return Err(Error::new_spanned(
+                                ty,
+                                "atomic is expected only for Atomic types, such as AtomicUsize",
+                            ))
+                        }
+                    };
+                    let method_load = config.method(&member, case, str::to_string);
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method(&self, v: #value, order: ::core::sync::atomic::Ordering) {
+                            self.#member.store(v, order)
+                        }
+                    });
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method_load(&self, order: ::core::sync::atomic::Ordering) -> #value {
+                            self.#member.load(order)
+                        }
+                    });
+
+                    return Ok(block);
+                }
+
+                // A path can be set from anything that borrows as one, such as `&str`
+                if config.as_ref {
+                    if !is_type(ty, "PathBuf") {
+                        return Err(Error::new_spanned(
+                            ty,
+                            "as_ref is expected only for PathBuf types",
+                        ));
+                    }
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method(&mut self, v: impl ::core::convert::AsRef<::std::path::Path>) {
+                            self.#member = v.as_ref().to_path_buf();
+                        }
+                    });
+                } else {
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method(&mut self, v: #ty) {
+                            self.#member = v;
+                        }
+                    });
+                }
+
+                block.stmts.push(parse_quote! {
+                    #(#attrs)*
+                    #track_caller
+                    pub fn #method_as_mut(&mut self) -> &mut #ty {
+                        &mut self.#member
+                    }
+                });
+
+                if config.modify {
+                    let method_modify = config.method(&member, case, |n| format!("modify_{}", n));
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method_modify(&mut self, f: impl ::core::ops::FnOnce(&mut #ty)) {
+                            f(&mut self.#member)
+                        }
+                    });
+                }
+
+                if config.swap {
+                    let method_swap = config.method(&member, case, |n| format!("swap_{}", n));
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method_swap(&mut self, other: &mut Self) {
+                            ::std::mem::swap(&mut self.#member, &mut other.#member)
+                        }
+                    });
+                }
+
+                if config.clearable {
+                    let method_clear = config.method(&member, case, |n| format!("clear_{}", n));
+                    let expr = config.default_expr(&field.ty)?;
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method_clear(&mut self) {
+                            self.#member = #expr;
+                        }
+                    });
+                }
+
+                if config.replace_with {
+                    let method_replace =
+                        config.method(&member, case, |n| format!("take_and_replace_{}", n));
+                    let method_replace_with =
+                        config.method(&member, case, |n| format!("replace_{}_with", n));
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method_replace(&mut self, v: #ty) -> #ty {
+                            ::std::mem::replace(&mut self.#member, v)
+                        }
+                    });
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method_replace_with(&mut self, f: impl ::core::ops::FnOnce(&#ty) -> #ty) -> #ty {
+                            let v = f(&self.#member);
+                            ::std::mem::replace(&mut self.#member, v)
+                        }
+                    });
+                }
+
+                if container.patch || container.reflect || container.copy_from {
+                    let ident = match &member {
+                        Member::Named(ident) => ident.clone(),
+                        Member::Unnamed(i) => format_ident!("field_{}", i.index),
+                    };
+
+                    assigned.push((cfg_attrs(&field.attrs), member.clone(), ident, ty.clone()));
+                }
+
+                if config.get_or_insert {
+                    let inner = match type_argument(ty, "Option") {
+                        Some(ty) => ty,
+                        None => {
+                            return Err(Error::new_spanned(
+                                ty,
+                                "get_or_insert is expected only for Option types",
+                            ))
+                        }
+                    };
+                    let method_or_insert =
+                        config.method(&member, case, |n| format!("{}_or_insert_with", n));
+
+                    block.stmts.push(parse_quote! {
+                        #(#attrs)*
+                        #track_caller
+                        pub fn #method_or_insert(&mut self, f: impl ::core::ops::FnOnce() -> #inner) -> &mut #inner {
+                            self.#member.get_or_insert_with(f)
+                        }
+                    });
+                }
+
+                Ok::<_, Error>(block)
+            },
+        )?;
+
+    if container.copy_from {
+        let assignments = assigned.iter().map(|(attrs, member, _, _)| {
+            quote! {
+                #(#attrs)*
+                {
+                    self.#member = ::core::clone::Clone::clone(&other.#member);
+                }
+            }
+        });
+
+        block.stmts.push(parse_quote! {
+            /// Set every attribute to a clone of the attribute of `other`
+            pub fn set_all(&mut self, other: &Self) {
+                #(#assignments)*
+            }
+        });
+    }
+
+    let patch = if container.patch {
+        let patch_name = format_ident!("{}Patch", name);
+        // Parameters used only by the skipped attributes are left out of the patch
+        let patch_generics = used_generics(generics, assigned.iter().map(|(_, _, _, ty)| ty));
+        let (patch_impl_generics, patch_ty_generics, patch_where) = patch_generics.split_for_impl();
+        let fields = assigned.iter().map(|(attrs, _, ident, ty)| {
+            quote! { #(#attrs)* pub #ident: ::core::option::Option<#ty> }
+        });
+        let nones = assigned.iter().map(|(attrs, _, ident, _)| {
+            quote! { #(#attrs)* #ident: ::core::option::Option::None }
+        });
+        let assignments = assigned.iter().map(|(attrs, member, ident, _)| {
+            quote! {
+                #(#attrs)*
+                if let ::core::option::Option::Some(v) = patch.#ident {
+                    self.#member = v;
+                }
+            }
+        });
+
+        block.stmts.push(parse_quote! {
+            /// Set every attribute provided by `patch`, keeping the others
+            pub fn apply_patch(&mut self, patch: #patch_name #patch_ty_generics) {
+                #(#assignments)*
+            }
+        });
+
+        quote! {
+            /// Partial update of the attributes, applied with `apply_patch`
+            #vis struct #patch_name #patch_generics #patch_where {
+                #(#fields,)*
+            }
+
+            impl #patch_impl_generics ::core::default::Default for #patch_name #patch_ty_generics #patch_where {
+                fn default() -> Self {
+                    Self {
+                        #(#nones,)*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let reflect =
+        if container.reflect {
+            let field_name = format_ident!("{}Field", name);
+            let variants: Vec<(&Vec<Attribute>, Ident)> = assigned
+                .iter()
+                .map(|(attrs, _, ident, _)| {
+                    let variant = Case::Pascal.apply(&ident.to_string());
+
+                    (attrs, Ident::new(&variant, ident.span()))
+                })
+                .collect();
+            let declarations = variants.iter().map(|(attrs, variant)| {
+                quote! { #(#attrs)* #variant }
+            });
+            let arms = assigned.iter().zip(variants.iter()).map(
+                |((attrs, member, _, ty), (_, variant))| {
+                    quote! {
+                        #(#attrs)*
+                        #field_name::#variant => {
+                            self.#member = *value.downcast::<#ty>()?;
+                        }
+                    }
+                },
+            );
+
+            // Downcasting requires the attribute types to be `'static`, which only generic
+            // structs don't guarantee
+            let statics = if generics.params.is_empty() {
+                quote! {}
+            } else {
+                let types = assigned.iter().map(|(_, _, _, ty)| ty);
+
+                quote! { where #(#types: 'static),* }
+            };
+
+            block.stmts.push(parse_quote! {
+                /// Set the attribute of `field` to `value`, or return `value` back if it is not of the
+                /// attribute type
+                pub fn set(
+                    &mut self,
+                    field: #field_name,
+                    value: ::std::boxed::Box<dyn ::core::any::Any>,
+                ) -> ::core::result::Result<(), ::std::boxed::Box<dyn ::core::any::Any>> #statics {
+                    match field {
+                        #(#arms)*
+                    }
+
+                    ::core::result::Result::Ok(())
+                }
+            });
+
+            quote! {
+                /// Attributes that can be set with `set`
+                #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+                #vis enum #field_name {
+                    #(#declarations,)*
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+    if container.inline {
+        block.stmts.iter_mut().for_each(|s| {
+            if let Stmt::Item(Item::Fn(f)) = s {
+                f.attrs.push(parse_quote! { #[inline] });
+            }
+        });
+    }
+
+    let expanded = quote! {
+        #patch
+
+        #reflect
+
+        impl #impl_generics #name #ty_generics #where_clause #block
+    };
+
+    collision::check(&expanded)?;
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::accessor_field;
+
+    #[test]
+    fn track_caller() {
+        let expanded = expand(
+            parse_quote! {
+                #[unprolix(track_caller)]
+                struct Tracked {
+                    a: u8,
+                }
+            },
+            accessor_field,
+        )
+        .unwrap()
+        .to_string()
+        .replace(' ', "");
+
+        assert!(expanded.contains("#[track_caller]pubfnset_a("));
+        assert!(expanded.contains("#[track_caller]pubfna_as_mut("));
+    }
+
+    #[test]
+    fn inline() {
+        let expanded = expand(
+            parse_quote! {
+                #[unprolix(inline)]
+                struct Brush {
+                    width: u8,
+                }
+            },
+            accessor_field,
+        )
+        .unwrap()
+        .to_string()
+        .replace(' ', "");
+
+        assert!(expanded.contains("#[inline]pubfnset_width("));
+    }
+}
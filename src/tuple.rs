@@ -0,0 +1,154 @@
+use crate::config::{cfg_attrs, fields};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, DeriveInput, Ident, Member};
+
+/// `cfg` predicate under which every attribute of `attrs` is compiled
+fn predicate(attrs: &[Attribute]) -> TokenStream {
+    let predicates = attrs.iter().map(|a| {
+        let tokens = &a.tokens;
+
+        quote! { all #tokens }
+    });
+
+    quote! { all(#(#predicates),*) }
+}
+
+/// Every combination of the gated tuple elements being compiled or not, as the `cfg` attribute
+/// selecting it along with whether each element is compiled
+///
+/// The tuple type can't carry `cfg` attributes, so the conversion is implemented once per
+/// combination. Without gated elements, there is a single unconditional combination.
+fn combinations(gated: &[TokenStream]) -> Vec<(TokenStream, Vec<bool>)> {
+    if gated.is_empty() {
+        return vec![(quote! {}, vec![])];
+    }
+
+    (0..1usize << gated.len())
+        .map(|mask| {
+            let compiled: Vec<bool> = (0..gated.len()).map(|i| mask & (1 << i) != 0).collect();
+            let predicates = gated.iter().zip(compiled.iter()).map(|(p, c)| match c {
+                true => quote! { #p },
+                false => quote! { not(#p) },
+            });
+
+            (quote! { #[cfg(all(#(#predicates),*))] }, compiled)
+        })
+        .collect()
+}
+
+pub(crate) fn expand_into(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = fields(input.data)?;
+    let gated: Vec<TokenStream> = fields
+        .iter()
+        .map(|(_, field, _)| cfg_attrs(&field.attrs))
+        .filter(|cfg| !cfg.is_empty())
+        .map(|cfg| predicate(&cfg))
+        .collect();
+
+    let impls = combinations(&gated).into_iter().map(|(cfg, compiled)| {
+        let mut compiled = compiled.into_iter();
+        let (members, types): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .filter(|(_, field, _)| {
+                cfg_attrs(&field.attrs).is_empty() || compiled.next().unwrap_or(true)
+            })
+            .map(|(member, field, _)| (member, &field.ty))
+            .unzip();
+
+        quote! {
+            #cfg
+            impl #impl_generics ::core::convert::From<#name #ty_generics> for (#(#types,)*) #where_clause {
+                fn from(v: #name #ty_generics) -> Self {
+                    (#(v.#members,)*)
+                }
+            }
+        }
+    });
+
+    Ok(quote! { #(#impls)* })
+}
+
+pub(crate) fn expand_from(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Attributes set to a value, which keep their `cfg`, or taken from the tuple
+    enum Value {
+        Set(TokenStream),
+        Element(Member, Ident, TokenStream, bool),
+    }
+
+    let mut values = vec![];
+    let mut gated = vec![];
+
+    for (member, field, config) in fields(input.data)? {
+        let cfg = cfg_attrs(&field.attrs);
+
+        if config.skip {
+            values.push(Value::Set(
+                quote! { #(#cfg)* #member: ::core::default::Default::default() },
+            ));
+        } else if config.default {
+            let expr = config.default_expr(&field.ty)?;
+
+            values.push(Value::Set(quote! { #(#cfg)* #member: #expr }));
+        } else {
+            let binding = match &member {
+                Member::Named(ident) => ident.clone(),
+                Member::Unnamed(i) => format_ident!("field_{}", i.index),
+            };
+
+            if !cfg.is_empty() {
+                gated.push(predicate(&cfg));
+            }
+
+            let ty = &field.ty;
+
+            values.push(Value::Element(
+                member,
+                binding,
+                quote! { #ty },
+                !cfg.is_empty(),
+            ));
+        }
+    }
+
+    let impls = combinations(&gated).into_iter().map(|(cfg, compiled)| {
+        let mut compiled = compiled.into_iter();
+        let mut bindings = vec![];
+        let mut types = vec![];
+        let mut initialized = vec![];
+
+        for value in values.iter() {
+            match value {
+                Value::Set(v) => initialized.push(v.clone()),
+                Value::Element(member, binding, ty, gated) => {
+                    if !gated || compiled.next().unwrap_or(true) {
+                        initialized.push(quote! { #member: #binding });
+                        bindings.push(binding);
+                        types.push(ty);
+                    }
+                }
+            }
+        }
+
+        quote! {
+            #cfg
+            impl #impl_generics ::core::convert::From<(#(#types,)*)> for #name #ty_generics #where_clause {
+                fn from(t: (#(#types,)*)) -> Self {
+                    let (#(#bindings,)*) = t;
+
+                    Self {
+                        #(#initialized),*
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote! { #(#impls)* })
+}
@@ -0,0 +1,308 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, Expr, GenericArgument, GenericParam, Generics, Ident, PathArguments, PathSegment,
+    Type, WherePredicate,
+};
+
+/// Last segment of a plain path type
+///
+/// Qualified paths such as `<T as Trait>::Assoc` have no meaningful last segment to inspect.
+fn last_segment(ty: &Type) -> Option<&PathSegment> {
+    match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.iter().last(),
+        _ => None,
+    }
+}
+
+/// Whether any segment of a plain path type is `name`, such as `rc` in `std::rc::Weak<T>`
+pub(crate) fn has_segment(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.iter().any(|s| s.ident == name),
+        _ => false,
+    }
+}
+
+pub(crate) fn is_type(ty: &Type, name: &str) -> bool {
+    last_segment(ty).map(|s| s.ident == name).unwrap_or(false)
+}
+
+pub(crate) fn type_arguments(ty: &Type, wrapper: &str) -> Option<Vec<GenericArgument>> {
+    let segment = last_segment(ty)?;
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(a) if segment.ident == wrapper => {
+            Some(a.args.iter().cloned().collect())
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn type_argument(ty: &Type, wrapper: &str) -> Option<Type> {
+    type_arguments(ty, wrapper)?
+        .into_iter()
+        .find_map(|a| match a {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        })
+}
+
+/// First type argument of a path type, whatever the wrapper is
+pub(crate) fn first_type_argument(ty: &Type) -> Option<Type> {
+    match &last_segment(ty)?.arguments {
+        PathArguments::AngleBracketed(a) => a.args.iter().find_map(|a| match a {
+            GenericArgument::Type(t) => Some(t.clone()),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Default value of a primitive type that can be used in const contexts
+pub(crate) fn const_default(ty: &Type) -> Option<Expr> {
+    let segment = match ty {
+        Type::Path(p) if p.qself.is_none() && p.path.segments.len() == 1 => &p.path.segments[0],
+        Type::Tuple(t) if t.elems.is_empty() => return Some(parse_quote!(())),
+        _ => return None,
+    };
+
+    if !segment.arguments.is_empty() {
+        return None;
+    }
+
+    let expr = match segment.ident.to_string().as_str() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" => parse_quote!(0),
+        "f32" | "f64" => parse_quote!(0.0),
+        "bool" => parse_quote!(false),
+        "char" => parse_quote!('\0'),
+        _ => return None,
+    };
+
+    Some(expr)
+}
+
+/// Value type of an atomic type, such as `usize` for `AtomicUsize`
+pub(crate) fn atomic_value(ty: &Type) -> Option<Type> {
+    let segment = last_segment(ty)?;
+    let name = segment.ident.to_string();
+
+    if name == "AtomicPtr" {
+        let ty = type_argument(ty, "AtomicPtr")?;
+
+        return Some(parse_quote!(*mut #ty));
+    }
+
+    match name.strip_prefix("Atomic")? {
+        value @ ("Bool" | "I8" | "I16" | "I32" | "I64" | "Isize" | "U8" | "U16" | "U32" | "U64"
+        | "Usize") => syn::parse_str(&value.to_lowercase()).ok(),
+        _ => None,
+    }
+}
+
+/// Primitive type of a non-zero integer type, such as `u32` for `NonZeroU32` or `NonZero<u32>`
+pub(crate) fn non_zero_value(ty: &Type) -> Option<Type> {
+    let segment = last_segment(ty)?;
+
+    if segment.ident == "NonZero" {
+        return type_argument(ty, "NonZero");
+    }
+
+    match segment.ident.to_string().strip_prefix("NonZero")? {
+        value @ ("I8" | "I16" | "I32" | "I64" | "I128" | "Isize" | "U8" | "U16" | "U32" | "U64"
+        | "U128" | "Usize") => syn::parse_str(&value.to_lowercase()).ok(),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_primitive(ty: &Type) -> bool {
+    const_default(ty).is_some()
+}
+
+/// Whether a type is a shared reference, such as `&'a str`, which is `Copy`
+pub(crate) fn is_shared_ref(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => r.mutability.is_none(),
+        Type::Paren(p) => is_shared_ref(&p.elem),
+        Type::Group(g) => is_shared_ref(&g.elem),
+        _ => false,
+    }
+}
+
+/// Whether a type is known not to implement `Default`, such as function pointers, raw pointers
+/// and references other than `&str` and `&[T]`
+///
+/// This is a best-effort check, since most types can't be resolved by the macro.
+pub(crate) fn lacks_default(ty: &Type) -> bool {
+    match ty {
+        Type::BareFn(_) | Type::Ptr(_) => true,
+        Type::Reference(r) => !matches!(&*r.elem, Type::Slice(_)) && !is_type(&r.elem, "str"),
+        Type::Paren(p) => lacks_default(&p.elem),
+        Type::Group(g) => lacks_default(&g.elem),
+        _ => false,
+    }
+}
+
+/// Whether a type refers to any of the given identifiers, such as the type parameters of a struct
+pub(crate) fn mentions(ty: &Type, idents: &[Ident]) -> bool {
+    fn walk(tokens: TokenStream, idents: &[Ident]) -> bool {
+        tokens.into_iter().any(|t| match t {
+            TokenTree::Ident(i) => idents.contains(&i),
+            TokenTree::Group(g) => walk(g.stream(), idents),
+            _ => false,
+        })
+    }
+
+    walk(ty.to_token_stream(), idents)
+}
+
+/// Identifiers and lifetimes mentioned by `tokens`, such as `T` and `a` in `&'a [T]`, added once
+fn names(tokens: TokenStream, idents: &mut Vec<Ident>, lifetimes: &mut Vec<Ident>) {
+    let mut lifetime = false;
+
+    for t in tokens {
+        let quote = matches!(&t, TokenTree::Punct(p) if p.as_char() == '\'');
+
+        match t {
+            TokenTree::Ident(i) if lifetime && !lifetimes.contains(&i) => lifetimes.push(i),
+            TokenTree::Ident(i) if !lifetime && !idents.contains(&i) => idents.push(i),
+            TokenTree::Group(g) => names(g.stream(), idents, lifetimes),
+            _ => (),
+        }
+
+        lifetime = quote;
+    }
+}
+
+/// Parameters of `generics` used by any of `types`, directly or through the bounds of a used
+/// parameter, along with the where predicates that refer only to them
+///
+/// A generated struct declaring an unused type or lifetime parameter is rejected with E0392.
+pub(crate) fn used_generics<'a>(
+    generics: &Generics,
+    types: impl IntoIterator<Item = &'a Type>,
+) -> Generics {
+    let name = |p: &GenericParam| match p {
+        GenericParam::Type(t) => (t.ident.clone(), false),
+        GenericParam::Lifetime(l) => (l.lifetime.ident.clone(), true),
+        GenericParam::Const(c) => (c.ident.clone(), false),
+    };
+    let predicates: Vec<&WherePredicate> = generics
+        .where_clause
+        .iter()
+        .flat_map(|w| w.predicates.iter())
+        .collect();
+
+    let mut idents = vec![];
+    let mut lifetimes = vec![];
+
+    types
+        .into_iter()
+        .for_each(|ty| names(ty.to_token_stream(), &mut idents, &mut lifetimes));
+
+    let used = |p: &GenericParam, idents: &[Ident], lifetimes: &[Ident]| match name(p) {
+        (ident, true) => lifetimes.contains(&ident),
+        (ident, false) => idents.contains(&ident),
+    };
+
+    // The bounds of a used parameter may use further parameters, until none is added
+    loop {
+        let count = idents.len() + lifetimes.len();
+        let mut bounds = TokenStream::new();
+
+        for p in generics.params.iter() {
+            if used(p, &idents, &lifetimes) {
+                match p {
+                    GenericParam::Type(t) => t.bounds.to_tokens(&mut bounds),
+                    GenericParam::Lifetime(l) => l.bounds.to_tokens(&mut bounds),
+                    GenericParam::Const(c) => c.ty.to_tokens(&mut bounds),
+                }
+            }
+        }
+
+        for w in predicates.iter() {
+            let mut left = (vec![], vec![]);
+
+            match w {
+                WherePredicate::Type(t) => {
+                    names(t.bounded_ty.to_token_stream(), &mut left.0, &mut left.1)
+                }
+                WherePredicate::Lifetime(l) => left.1.push(l.lifetime.ident.clone()),
+                WherePredicate::Eq(e) => {
+                    names(e.lhs_ty.to_token_stream(), &mut left.0, &mut left.1)
+                }
+            }
+
+            if left.0.iter().any(|i| idents.contains(i))
+                || left.1.iter().any(|l| lifetimes.contains(l))
+            {
+                w.to_tokens(&mut bounds);
+            }
+        }
+
+        names(bounds, &mut idents, &mut lifetimes);
+
+        if idents.len() + lifetimes.len() == count {
+            break;
+        }
+    }
+
+    let params: Vec<&GenericParam> = generics
+        .params
+        .iter()
+        .filter(|p| used(p, &idents, &lifetimes))
+        .collect();
+    let unused: Vec<(Ident, bool)> = generics
+        .params
+        .iter()
+        .filter(|p| !used(p, &idents, &lifetimes))
+        .map(name)
+        .collect();
+
+    // A predicate mentioning a removed parameter would not compile
+    let predicates: Vec<&WherePredicate> = predicates
+        .into_iter()
+        .filter(|w| {
+            let (mut i, mut l) = (vec![], vec![]);
+
+            names(w.to_token_stream(), &mut i, &mut l);
+
+            !unused.iter().any(|(ident, lifetime)| match lifetime {
+                true => l.contains(ident),
+                false => i.contains(ident),
+            })
+        })
+        .collect();
+
+    let mut used: Generics = parse_quote! { <#(#params),*> };
+
+    if !predicates.is_empty() {
+        used.where_clause = Some(parse_quote! { where #(#predicates),* });
+    }
+
+    used
+}
+
+/// `PhantomData` of every type and lifetime parameter of `generics`, for generated structs whose
+/// attributes may not use all of them
+pub(crate) fn phantom(generics: &Generics) -> Option<Type> {
+    let types: Vec<TokenStream> = generics
+        .params
+        .iter()
+        .filter_map(|p| match p {
+            GenericParam::Type(t) => Some(t.ident.to_token_stream()),
+            GenericParam::Lifetime(l) => {
+                let lifetime = &l.lifetime;
+
+                Some(quote! { &#lifetime () })
+            }
+            GenericParam::Const(_) => None,
+        })
+        .collect();
+
+    if types.is_empty() {
+        None
+    } else {
+        Some(parse_quote!(::core::marker::PhantomData<fn() -> (#(#types,)*)>))
+    }
+}
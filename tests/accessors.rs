@@ -0,0 +1,54 @@
+use unprolix::Accessors;
+
+#[derive(Accessors)]
+struct Account {
+    #[unprolix(get)]
+    id: u32,
+    #[unprolix(set)]
+    password: String,
+    balance: u64,
+}
+
+impl Account {
+    // These would clash with the generated methods if they were emitted
+    fn set_id(&mut self) {}
+
+    fn password(&self) {}
+}
+
+#[test]
+fn read_only_and_write_only() {
+    let mut a = Account {
+        id: 1,
+        password: String::new(),
+        balance: 0,
+    };
+
+    a.set_id();
+    a.password();
+
+    a.set_password(String::from("secret"));
+    a.set_balance(10);
+
+    assert_eq!(1, *a.id());
+    assert_eq!(10, *a.balance());
+    assert_eq!("secret", a.password);
+}
+
+// The load getter is generated once, by the setters
+#[derive(Accessors)]
+struct Gauge {
+    #[unprolix(atomic)]
+    level: std::sync::atomic::AtomicU32,
+}
+
+#[test]
+fn atomic() {
+    let g = Gauge {
+        level: std::sync::atomic::AtomicU32::new(1),
+    };
+
+    g.set_level(2, std::sync::atomic::Ordering::Relaxed);
+
+    assert_eq!(2, g.level(std::sync::atomic::Ordering::Relaxed));
+}
@@ -0,0 +1,79 @@
+use unprolix::Builder;
+
+#[derive(Debug, PartialEq, Builder)]
+struct Request {
+    a: u8,
+    #[unprolix(default = "a as u32 * 10")]
+    timeout: u32,
+    #[unprolix(default)]
+    headers: Vec<String>,
+    #[unprolix(skip)]
+    attempts: u8,
+}
+
+#[test]
+fn builder() {
+    assert_eq!(
+        Ok(Request {
+            a: 1,
+            timeout: 10,
+            headers: vec![],
+            attempts: 0,
+        }),
+        Request::builder().a(1).build()
+    );
+
+    assert_eq!(
+        Ok(Request {
+            a: 1,
+            timeout: 5,
+            headers: vec![String::from("accept")],
+            attempts: 0,
+        }),
+        Request::builder()
+            .a(1)
+            .timeout(5)
+            .headers(vec![String::from("accept")])
+            .build()
+    );
+}
+
+#[test]
+fn builder_missing_attribute() {
+    assert_eq!(Err("a is not set"), Request::builder().timeout(5).build());
+}
+
+#[derive(Builder)]
+struct Labeled<'a, T> {
+    label: &'a str,
+    value: T,
+}
+
+#[test]
+fn generic_builder() {
+    let l = Labeled::builder().label("x").value(1u8).build().unwrap();
+
+    assert_eq!("x", l.label);
+    assert_eq!(1, l.value);
+}
+
+#[derive(Builder)]
+struct Batch<'a, T, U> {
+    label: &'a str,
+    value: T,
+    #[unprolix(skip)]
+    pending: Vec<U>,
+}
+
+#[test]
+fn generic_builder_skipped_attribute() {
+    let b = Batch::<u8, String>::builder()
+        .label("x")
+        .value(1)
+        .build()
+        .unwrap();
+
+    assert_eq!("x", b.label);
+    assert_eq!(1, b.value);
+    assert!(b.pending.is_empty());
+}
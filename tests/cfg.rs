@@ -0,0 +1,186 @@
+use unprolix::{Constructor, FromTuple, Getters, IntoTuple, MutGetters, Reset, Setters};
+
+// The test-only __test-cfg feature gates the attributes, so both cases are tested by enabling it
+// or not
+
+#[derive(Constructor)]
+struct Connection {
+    host: String,
+    #[cfg(feature = "__test-cfg")]
+    port: u16,
+    #[cfg(not(feature = "__test-cfg"))]
+    #[unprolix(default = "80")]
+    port: u16,
+}
+
+#[derive(Constructor)]
+#[unprolix(named_args)]
+struct Endpoint {
+    path: String,
+    #[cfg(feature = "__test-cfg")]
+    secure: bool,
+}
+
+#[cfg(feature = "__test-cfg")]
+#[test]
+fn cfg_enabled() {
+    let c = Connection::new(String::from("localhost"), 8080);
+    let e = Endpoint::new(EndpointArgs {
+        path: String::from("/"),
+        secure: true,
+    });
+
+    assert_eq!("localhost", c.host);
+    assert_eq!(8080, c.port);
+    assert_eq!("/", e.path);
+    assert!(e.secure);
+}
+
+#[cfg(not(feature = "__test-cfg"))]
+#[test]
+fn cfg_disabled() {
+    let c = Connection::new(String::from("localhost"));
+    let e = Endpoint::new(EndpointArgs {
+        path: String::from("/"),
+    });
+
+    assert_eq!("localhost", c.host);
+    assert_eq!(80, c.port);
+    assert_eq!("/", e.path);
+}
+
+#[derive(Getters, MutGetters, Setters)]
+struct Sensor {
+    value: u32,
+    #[cfg(feature = "__test-cfg")]
+    offset: u32,
+}
+
+impl Sensor {
+    // Would clash with the generated methods if the field was compiled
+    #[cfg(not(feature = "__test-cfg"))]
+    fn offset(&self) -> u32 {
+        0
+    }
+
+    #[cfg(not(feature = "__test-cfg"))]
+    fn set_offset(&mut self, v: u32) {
+        self.value += v;
+    }
+}
+
+#[cfg(feature = "__test-cfg")]
+#[test]
+fn accessors_cfg_enabled() {
+    let mut s = Sensor {
+        value: 1,
+        offset: 0,
+    };
+
+    s.set_offset(2);
+    *s.offset_mut() += 1;
+
+    assert_eq!(1, *s.value());
+    assert_eq!(3, *s.offset());
+}
+
+#[cfg(not(feature = "__test-cfg"))]
+#[test]
+fn accessors_cfg_disabled() {
+    let mut s = Sensor { value: 1 };
+
+    s.set_offset(2);
+
+    assert_eq!(3, *s.value());
+    assert_eq!(0, s.offset());
+}
+
+#[derive(Getters, Setters)]
+struct Probe {
+    #[unprolix(test_only)]
+    samples: Vec<u32>,
+}
+
+// Integration tests are compiled with cfg(test), so the methods exist here
+#[test]
+fn test_only() {
+    let mut p = Probe { samples: vec![1] };
+
+    p.set_samples(vec![2, 3]);
+
+    assert_eq!(&vec![2, 3], p.samples());
+}
+
+#[derive(Debug, PartialEq, Reset)]
+struct Counter {
+    hits: u32,
+    #[cfg(feature = "__test-cfg")]
+    misses: u32,
+}
+
+#[test]
+fn reset_cfg() {
+    let mut c = Counter {
+        hits: 1,
+        #[cfg(feature = "__test-cfg")]
+        misses: 2,
+    };
+
+    c.reset();
+
+    assert_eq!(0, c.hits);
+    assert_eq!(0, misses(&c));
+}
+
+#[cfg(feature = "__test-cfg")]
+fn misses(c: &Counter) -> u32 {
+    c.misses
+}
+
+#[cfg(not(feature = "__test-cfg"))]
+fn misses(_: &Counter) -> u32 {
+    0
+}
+
+#[derive(Debug, PartialEq, FromTuple, IntoTuple)]
+struct Range {
+    start: u32,
+    #[cfg(feature = "__test-cfg")]
+    step: u32,
+    end: u32,
+    #[cfg(not(feature = "__test-cfg"))]
+    #[unprolix(default = "1")]
+    inclusive: u8,
+}
+
+#[cfg(feature = "__test-cfg")]
+#[test]
+fn tuple_cfg_enabled() {
+    let r = Range::from((1, 2, 3));
+
+    assert_eq!(
+        Range {
+            start: 1,
+            step: 2,
+            end: 3
+        },
+        r
+    );
+    assert_eq!((1, 2, 3), <(u32, u32, u32)>::from(r));
+}
+
+#[cfg(not(feature = "__test-cfg"))]
+#[test]
+fn tuple_cfg_disabled() {
+    let r = Range::from((1, 3));
+
+    assert_eq!(
+        Range {
+            start: 1,
+            end: 3,
+            inclusive: 1
+        },
+        r
+    );
+    assert_eq!((1, 3, 1), <(u32, u32, u8)>::from(r));
+}
@@ -0,0 +1,63 @@
+use unprolix::Unprolix;
+
+#[derive(Unprolix)]
+struct All {
+    a: u8,
+}
+
+#[test]
+fn all_parts() {
+    let mut s = All::new(1);
+
+    s.set_a(2);
+
+    assert_eq!(&2, s.a());
+}
+
+#[derive(Unprolix)]
+#[unprolix(getters)]
+struct GettersOnly {
+    a: u8,
+}
+
+impl GettersOnly {
+    // These would clash with the generated methods if they were emitted
+    fn new() -> Self {
+        Self { a: 3 }
+    }
+
+    fn set_a(&mut self) {
+        self.a += 1;
+    }
+}
+
+#[test]
+fn getters_only() {
+    let mut s = GettersOnly::new();
+
+    s.set_a();
+
+    assert_eq!(&4, s.a());
+}
+
+#[derive(Unprolix)]
+#[unprolix(constructor, setters)]
+struct ConstructorAndSetters {
+    a: u8,
+}
+
+impl ConstructorAndSetters {
+    // Would clash with the generated getter if it was emitted
+    fn a(&self) -> u8 {
+        self.a
+    }
+}
+
+#[test]
+fn constructor_and_setters() {
+    let mut s = ConstructorAndSetters::new(1);
+
+    *s.a_as_mut() += 1;
+
+    assert_eq!(2, s.a());
+}
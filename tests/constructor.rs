@@ -0,0 +1,851 @@
+use unprolix::Constructor;
+
+// Regression: a field carrying only a foreign `default`, such as `#[serde(default)]`, must still
+// be a parameter of `new`
+#[allow(unknown_lints)]
+#[derive(Debug, PartialEq, Constructor)]
+struct Foreign {
+    #[allow(default)]
+    a: u8,
+    #[unprolix(default)]
+    b: u8,
+}
+
+#[test]
+fn foreign_default_ignored() {
+    assert_eq!(Foreign { a: 5, b: 0 }, Foreign::new(5));
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+struct Samples {
+    name: String,
+    #[unprolix(from_slice)]
+    values: Vec<i32>,
+}
+
+#[test]
+fn from_slice() {
+    assert_eq!(
+        Samples {
+            name: String::from("s"),
+            values: vec![1, 2, 3],
+        },
+        Samples::new(String::from("s"), &[1, 2, 3])
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(named_args)]
+struct Window {
+    width: u32,
+    height: u32,
+    title: String,
+    #[unprolix(default)]
+    visible: bool,
+}
+
+#[test]
+fn named_args() {
+    let w = Window::new(WindowArgs {
+        width: 640,
+        height: 480,
+        title: String::from("main"),
+    });
+
+    assert_eq!(
+        Window {
+            width: 640,
+            height: 480,
+            title: String::from("main"),
+            visible: false,
+        },
+        w
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+struct Retry {
+    attempts: u8,
+    #[unprolix(default, overridable)]
+    delay: u64,
+}
+
+#[test]
+fn overridable_default() {
+    assert_eq!(
+        Retry {
+            attempts: 3,
+            delay: 0
+        },
+        Retry::new(3, None)
+    );
+    assert_eq!(
+        Retry {
+            attempts: 3,
+            delay: 100
+        },
+        Retry::new(3, Some(100))
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+struct Invoice {
+    net: u32,
+    tax: u32,
+    #[unprolix(default = "net + tax")]
+    total: u32,
+    #[unprolix(default = "1", overridable)]
+    copies: u8,
+}
+
+#[test]
+fn default_expression() {
+    assert_eq!(
+        Invoice {
+            net: 100,
+            tax: 20,
+            total: 120,
+            copies: 1,
+        },
+        Invoice::new(100, 20, None)
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(const_fn)]
+struct Limits {
+    max: u32,
+    #[unprolix(default = "0")]
+    min: u32,
+}
+
+const LIMITS: Limits = Limits::new(10);
+
+#[test]
+fn const_fn() {
+    assert_eq!(Limits { max: 10, min: 0 }, LIMITS);
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(const_fn)]
+struct Flags {
+    id: u8,
+    #[unprolix(default)]
+    count: usize,
+    #[unprolix(default)]
+    ratio: f32,
+    #[unprolix(default)]
+    enabled: bool,
+    #[unprolix(default)]
+    marker: char,
+}
+
+const FLAGS: Flags = Flags::new(1);
+
+#[test]
+fn const_primitive_defaults() {
+    assert_eq!(
+        Flags {
+            id: 1,
+            count: 0,
+            ratio: 0.0,
+            enabled: false,
+            marker: '\0',
+        },
+        FLAGS
+    );
+}
+
+#[derive(Debug, Default, PartialEq, Constructor)]
+struct Session {
+    user: String,
+    #[unprolix(skip)]
+    token: Option<String>,
+    #[unprolix(skip)]
+    retries: u8,
+    #[unprolix(default = "30")]
+    timeout: u32,
+}
+
+#[test]
+fn skip_default_tail() {
+    assert_eq!(
+        Session {
+            user: String::from("ada"),
+            token: None,
+            retries: 0,
+            timeout: 30,
+        },
+        Session::new(String::from("ada"))
+    );
+}
+
+#[derive(Debug, Default, PartialEq, Constructor)]
+struct Slot<T> {
+    id: u8,
+    #[unprolix(skip)]
+    value: T,
+}
+
+#[test]
+fn skip_default_tail_generic() {
+    assert_eq!(
+        Slot {
+            id: 1,
+            value: String::new(),
+        },
+        Slot::<String>::new(1)
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(derive_default)]
+struct Backoff {
+    #[unprolix(default = "3")]
+    attempts: u8,
+    #[unprolix(default)]
+    delays: Vec<u64>,
+}
+
+#[test]
+fn derive_default_delegates_to_new() {
+    assert_eq!(Backoff::new(), Backoff::default());
+    assert_eq!(3, Backoff::default().attempts);
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(derive_default)]
+struct Viewport {
+    width: u32,
+    #[unprolix(default = "width * 2")]
+    height: u32,
+    #[unprolix(skip)]
+    title: String,
+}
+
+#[test]
+fn derive_default_field_by_field() {
+    let w = Viewport::default();
+
+    assert_eq!(0, w.width);
+    assert_eq!(0, w.height);
+    assert_eq!(String::new(), w.title);
+    assert_eq!(Viewport::new(2).height, 4);
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(derive_default)]
+struct Pane<T, U> {
+    content: T,
+    size: u8,
+    #[unprolix(skip)]
+    history: Vec<U>,
+    #[unprolix(skip)]
+    cache: Option<U>,
+}
+
+#[test]
+fn derive_default_generic() {
+    let p = Pane::<String, u8>::default();
+
+    assert_eq!(String::new(), p.content);
+    assert_eq!(0, p.size);
+    assert!(p.history.is_empty() && p.cache.is_none());
+    assert_eq!(3, Pane::<_, u8>::new(String::from("a"), 3).size);
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(defaults_last)]
+struct Profile {
+    #[unprolix(default = "age >= 18")]
+    adult: bool,
+    age: u8,
+    #[unprolix(default, overridable)]
+    nickname: Option<String>,
+    score: u32,
+}
+
+#[test]
+fn defaults_last() {
+    assert_eq!(
+        Profile {
+            adult: true,
+            age: 20,
+            nickname: None,
+            score: 5,
+        },
+        Profile::new(20, None, 5)
+    );
+}
+
+// Clippy rejects this test if the generated new lacks the too_many_arguments allow
+#[derive(Debug, PartialEq, Constructor)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+    h: u16,
+    s: u16,
+    l: u16,
+    name: &'static str,
+}
+
+#[test]
+fn many_arguments() {
+    let c = Color::new(1, 2, 3, 4, 5, 6, 7, "gray");
+
+    assert_eq!(1, c.r);
+    assert_eq!("gray", c.name);
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(derive_default)]
+struct Catalog {
+    #[unprolix(collect)]
+    ids: Vec<u32>,
+    #[unprolix(collect)]
+    tags: std::collections::HashSet<String>,
+}
+
+#[test]
+fn collect() {
+    let c = Catalog::new(1..4, vec![String::from("a"), String::from("a")]);
+
+    assert_eq!(vec![1, 2, 3], c.ids);
+    assert_eq!(1, c.tags.len());
+    assert!(Catalog::default().ids.is_empty());
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(singleton)]
+struct Policy {
+    #[unprolix(default)]
+    retries: u8,
+    #[unprolix(default = "\"policy\"")]
+    name: &'static str,
+    #[unprolix(default)]
+    strict: bool,
+}
+
+const POLICY: Policy = Policy::INSTANCE;
+
+#[test]
+fn singleton() {
+    assert_eq!(
+        Policy {
+            retries: 0,
+            name: "policy",
+            strict: false,
+        },
+        POLICY
+    );
+    assert_eq!(Policy::new(), POLICY);
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+struct Account {
+    #[unprolix(arg = "id")]
+    account_identifier: u64,
+    #[unprolix(arg = "tags", collect)]
+    account_tags: Vec<String>,
+}
+
+#[test]
+fn arg_name() {
+    assert_eq!(
+        Account {
+            account_identifier: 7,
+            account_tags: vec![String::from("a")],
+        },
+        Account::new(7, vec![String::from("a")])
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(named_args)]
+struct Login {
+    #[unprolix(arg = "user")]
+    login_user: String,
+}
+
+#[test]
+fn arg_name_named_args() {
+    assert_eq!(
+        Login {
+            login_user: String::from("ana"),
+        },
+        Login::new(LoginArgs {
+            user: String::from("ana"),
+        })
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum PortError {
+    Reserved(u16),
+    EmptyHost,
+}
+
+fn unreserved(port: &u16) -> Result<(), PortError> {
+    if *port < 1024 {
+        Err(PortError::Reserved(*port))
+    } else {
+        Ok(())
+    }
+}
+
+async fn resolvable(host: &str) -> Result<(), PortError> {
+    if host.is_empty() {
+        Err(PortError::EmptyHost)
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(try_new, error = "PortError")]
+struct Listener {
+    #[unprolix(validate = "unreserved")]
+    port: u16,
+    backlog: u32,
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(try_new, async, error = "PortError")]
+struct Remote {
+    #[unprolix(validate = "resolvable")]
+    host: String,
+}
+
+#[test]
+fn try_new() {
+    assert_eq!(
+        Ok(Listener {
+            port: 8080,
+            backlog: 16,
+        }),
+        Listener::try_new(8080, 16)
+    );
+    assert_eq!(Err(PortError::Reserved(80)), Listener::try_new(80, 16));
+}
+
+#[tokio::test]
+async fn try_new_async() {
+    assert_eq!(
+        Ok(Remote {
+            host: String::from("localhost"),
+        }),
+        Remote::try_new(String::from("localhost")).await
+    );
+    assert_eq!(
+        Err(PortError::EmptyHost),
+        Remote::try_new(String::new()).await
+    );
+}
+
+#[track_caller]
+fn located(_: &u8) -> Result<(), u32> {
+    Err(std::panic::Location::caller().line())
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(try_new, track_caller, error = "u32")]
+struct Located {
+    #[unprolix(validate = "located")]
+    a: u8,
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(checked_unchecked, track_caller, error = "u32")]
+struct CheckedLocated {
+    #[unprolix(validate = "located")]
+    a: u8,
+}
+
+#[test]
+fn track_caller_validated() {
+    assert_eq!(Err(line!()), Located::try_new(1));
+    assert_eq!(Err(line!()), CheckedLocated::new(1));
+}
+
+fn at_most_eight(v: &u32) -> Result<(), u32> {
+    if *v <= 8 {
+        Ok(())
+    } else {
+        Err(*v)
+    }
+}
+
+fn lowercase(v: &str) -> Result<(), ()> {
+    if v.chars().all(|c| c.is_lowercase()) {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+#[derive(Debug, Constructor)]
+#[unprolix(try_new, gen_error)]
+struct Shard {
+    #[unprolix(validate = "at_most_eight", error = "u32")]
+    replica_count: u32,
+    #[unprolix(validate = "lowercase")]
+    region: String,
+}
+
+#[test]
+fn gen_error() {
+    assert!(Shard::try_new(2, String::from("eu")).is_ok());
+
+    match Shard::try_new(9, String::from("eu")) {
+        Err(ShardError::ReplicaCount(9)) => (),
+        r => panic!("unexpected {:?}", r),
+    }
+
+    match Shard::try_new(2, String::from("EU")) {
+        Err(ShardError::Region) => (),
+        r => panic!("unexpected {:?}", r),
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Constructor)]
+#[unprolix(partial)]
+struct Pool {
+    url: String,
+    #[unprolix(default = "4")]
+    size: usize,
+    #[unprolix(default)]
+    lazy: bool,
+    #[unprolix(skip)]
+    open: usize,
+}
+
+#[test]
+fn partial() {
+    let p = Pool::new_partial(String::from("db")).lazy(true).finish();
+
+    assert_eq!(
+        Pool {
+            url: String::from("db"),
+            size: 4,
+            lazy: true,
+            open: 0,
+        },
+        p
+    );
+    assert_eq!(
+        Pool::new(String::from("db")),
+        Pool::new_partial(String::from("db")).finish()
+    );
+    assert_eq!(8, Pool::new_partial(String::new()).size(8).finish().size);
+}
+
+#[derive(Debug, Default, PartialEq, Constructor)]
+#[unprolix(partial)]
+struct Shelf<'a, T, U> {
+    label: &'a str,
+    #[unprolix(default)]
+    items: Vec<T>,
+    #[unprolix(skip)]
+    cache: Option<U>,
+}
+
+#[test]
+fn partial_unused_generics() {
+    let s = Shelf::<u8, String>::new_partial("a")
+        .items(vec![1])
+        .finish();
+
+    assert_eq!(
+        Shelf {
+            label: "a",
+            items: vec![1],
+            cache: None,
+        },
+        s
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(const_default)]
+struct Volume {
+    level: u8,
+    muted: bool,
+    #[unprolix(default = "100")]
+    max: u8,
+}
+
+const VOLUME: Volume = Volume::DEFAULT;
+
+#[test]
+fn const_default() {
+    assert_eq!(
+        Volume {
+            level: 0,
+            muted: false,
+            max: 100,
+        },
+        VOLUME
+    );
+
+    const LOUD: Volume = Volume::new(80, false);
+
+    assert_eq!(80, LOUD.level);
+}
+
+// Every kind of attribute initialized once, so the parity check of `new` holds
+#[derive(Debug, Default, PartialEq, Constructor)]
+#[unprolix(defaults_last)]
+struct Settings {
+    name: String,
+    #[unprolix(default = "3")]
+    retries: u8,
+    #[unprolix(default, overridable)]
+    verbose: bool,
+    #[unprolix(from_slice)]
+    hosts: Vec<String>,
+    #[unprolix(collect)]
+    ports: Vec<u16>,
+    #[unprolix(skip)]
+    cache: Vec<u8>,
+    #[unprolix(arg = "mode")]
+    settings_mode: u8,
+}
+
+#[test]
+fn all_attributes_initialized() {
+    let s = Settings::new(String::from("s"), None, &[String::from("h")], vec![80], 2);
+
+    assert_eq!(
+        Settings {
+            name: String::from("s"),
+            retries: 3,
+            verbose: false,
+            hosts: vec![String::from("h")],
+            ports: vec![80],
+            cache: vec![],
+            settings_mode: 2,
+        },
+        s
+    );
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(required_ref, derive_default)]
+struct Job {
+    queue: String,
+    tags: Vec<String>,
+    #[unprolix(default = "3")]
+    attempts: u8,
+}
+
+#[test]
+fn required_ref() {
+    let queue = String::from("mail");
+    let tags = vec![String::from("urgent")];
+
+    let a = Job::new(&queue, &tags);
+    let b = Job::new(&queue, &tags);
+
+    assert_eq!(a, b);
+    assert_eq!(
+        Job {
+            queue: String::from("mail"),
+            tags: vec![String::from("urgent")],
+            attempts: 3,
+        },
+        a
+    );
+    assert_eq!(
+        Job {
+            queue: String::new(),
+            tags: vec![],
+            attempts: 3,
+        },
+        Job::default()
+    );
+}
+
+#[derive(Constructor, Debug, PartialEq)]
+#[unprolix(inline)]
+struct Cursor {
+    line: usize,
+    column: usize,
+}
+
+#[test]
+fn inline() {
+    assert_eq!(Cursor { line: 1, column: 2 }, Cursor::new(1, 2));
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(checked_unchecked, error = "PortError")]
+struct Endpoint {
+    #[unprolix(validate = "unreserved")]
+    port: u16,
+    path: String,
+}
+
+#[derive(Debug, Constructor)]
+#[unprolix(checked_unchecked, gen_error)]
+struct Replica {
+    #[unprolix(validate = "at_most_eight", error = "u32")]
+    index: u32,
+}
+
+#[test]
+fn checked_unchecked() {
+    assert_eq!(
+        Ok(Endpoint {
+            port: 8080,
+            path: String::from("/"),
+        }),
+        Endpoint::new(8080, String::from("/"))
+    );
+    assert_eq!(
+        Err(PortError::Reserved(80)),
+        Endpoint::new(80, String::from("/"))
+    );
+
+    // The unchecked path doesn't run the validation functions
+    let unchecked = unsafe { Endpoint::new_unchecked(80, String::from("/")) };
+
+    assert_eq!(
+        Endpoint {
+            port: 80,
+            path: String::from("/"),
+        },
+        unchecked
+    );
+
+    match Replica::new(9) {
+        Err(ReplicaError::Index(9)) => (),
+        r => panic!("unexpected {:?}", r),
+    }
+
+    assert_eq!(9, unsafe { Replica::new_unchecked(9) }.index);
+}
+
+type Stage = Box<dyn Fn(u8) -> u8>;
+
+#[derive(Constructor)]
+struct Pipeline {
+    name: &'static str,
+    #[unprolix(default)]
+    stages: Vec<Stage>,
+    #[unprolix(default, overridable)]
+    fallback: Option<Stage>,
+}
+
+#[test]
+fn qualified_default() {
+    let p = Pipeline::new("identity", None);
+
+    assert_eq!("identity", p.name);
+    assert!(p.stages.is_empty());
+    assert!(p.fallback.is_none());
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(into)]
+struct Mailbox {
+    owner: String,
+    quota: u64,
+    #[unprolix(default)]
+    unread: u32,
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(into, where_clause, derive_default)]
+struct Label {
+    text: String,
+    weight: u64,
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+struct Folder {
+    #[unprolix(into)]
+    name: String,
+    depth: u8,
+}
+
+#[test]
+fn into() {
+    assert_eq!(
+        Mailbox {
+            owner: String::from("ana"),
+            quota: 1024,
+            unread: 0,
+        },
+        Mailbox::new("ana", 1024_u32)
+    );
+
+    // Named type parameters accept the same arguments
+    assert_eq!(
+        Label {
+            text: String::from("ana"),
+            weight: 1024,
+        },
+        Label::new("ana", 1024_u32)
+    );
+    assert_eq!(Label::default(), Label::new(String::new(), 0_u8));
+
+    assert_eq!(
+        Folder {
+            name: String::from("inbox"),
+            depth: 2,
+        },
+        Folder::new("inbox", 2)
+    );
+}
+
+pub mod stateless {
+    use unprolix::Constructor;
+
+    // `new` takes no arguments and there is no Default implementation, so it is generated with
+    // `new_without_default` allowed and the tests still pass Clippy with `-D warnings`
+    #[derive(Debug, PartialEq, Constructor)]
+    pub struct Clock {
+        #[unprolix(default = "1_000")]
+        resolution: u64,
+    }
+
+    impl Clock {
+        pub fn resolution(&self) -> u64 {
+            self.resolution
+        }
+    }
+}
+
+#[test]
+fn new_without_default() {
+    assert_eq!(1_000, stateless::Clock::new().resolution());
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+#[unprolix(named_args)]
+struct Bucket<'a, K, T>
+where
+    T: Clone,
+{
+    name: &'a str,
+    key: K,
+    #[unprolix(default)]
+    items: Vec<T>,
+}
+
+#[test]
+fn named_args_unused_generics() {
+    // `T` is only used by the defaulted attribute, so the arguments don't declare it
+    let b: Bucket<'_, u8, String> = Bucket::new(BucketArgs { name: "a", key: 1 });
+
+    assert_eq!(
+        Bucket {
+            name: "a",
+            key: 1,
+            items: vec![],
+        },
+        b
+    );
+}
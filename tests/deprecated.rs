@@ -0,0 +1,31 @@
+use unprolix::{Getters, Setters};
+
+#[derive(Getters, Setters)]
+struct Legacy {
+    #[deprecated(since = "0.2.0", note = "use id instead")]
+    key: u32,
+    id: u32,
+}
+
+#[derive(Getters)]
+#[unprolix(trait = "LegacyAccessors")]
+struct LegacyTrait {
+    #[deprecated]
+    key: u32,
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_accessors() {
+    let mut l = Legacy { key: 1, id: 2 };
+
+    l.set_key(3);
+    *l.key_as_mut() += 1;
+
+    assert_eq!(&4, l.key());
+    assert_eq!(&2, l.id());
+
+    let t = LegacyTrait { key: 5 };
+
+    assert_eq!(&5, LegacyAccessors::key(&t));
+}
@@ -0,0 +1,23 @@
+use unprolix::DisplayDelegate;
+
+#[derive(DisplayDelegate)]
+struct Label {
+    #[unprolix(display)]
+    text: String,
+    width: usize,
+}
+
+#[derive(DisplayDelegate)]
+struct Port(u16);
+
+#[test]
+fn display_delegate() {
+    let l = Label {
+        text: String::from("name"),
+        width: 8,
+    };
+
+    assert_eq!("name", l.to_string());
+    assert_eq!("    name", format!("{:>width$}", l, width = l.width));
+    assert_eq!("8080", Port(8080).to_string());
+}
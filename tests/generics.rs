@@ -0,0 +1,85 @@
+use unprolix::{Constructor, Getters, MutGetters, Reset, Setters};
+
+#[derive(Debug, PartialEq, Constructor, Getters, MutGetters, Reset, Setters)]
+struct Wrapper<'a, T: Default = u32>
+where
+    T: Clone,
+{
+    value: T,
+    #[unprolix(copy)]
+    label: &'a str,
+}
+
+#[test]
+fn defaulted_type_parameter() {
+    let mut w: Wrapper = Wrapper::new(5, "five");
+
+    assert_eq!(&5, w.value());
+    assert_eq!("five", w.label());
+
+    w.set_value(6);
+    *w.value_mut() += 1;
+    assert_eq!(&7, w.value());
+
+    w.reset();
+    assert_eq!(Wrapper::new(0, ""), w);
+}
+
+// Only compiles if the getter returns the reference with its own lifetime, not the borrow of w
+fn label<'a>(w: &Wrapper<'a>) -> &'a str {
+    w.label()
+}
+
+#[test]
+fn copy_reference_keeps_lifetime() {
+    let text = String::from("borrowed");
+    let l = {
+        let w: Wrapper = Wrapper::new(1, &text);
+
+        label(&w)
+    };
+
+    assert_eq!("borrowed", l);
+}
+
+#[derive(Getters)]
+#[unprolix(trait = "PairAccessors")]
+struct Pair<A, B = A> {
+    a: A,
+    b: B,
+}
+
+#[test]
+fn generic_trait_accessors() {
+    let p: Pair<u8> = Pair { a: 1, b: 2 };
+
+    assert_eq!(3, p.a() + p.b());
+}
+
+struct NotDefault;
+
+#[derive(Constructor)]
+struct Stack<T> {
+    #[unprolix(default)]
+    items: Vec<T>,
+}
+
+#[derive(Constructor)]
+struct Slot<T> {
+    #[unprolix(default)]
+    value: T,
+    #[unprolix(default, overridable)]
+    fallback: Option<T>,
+}
+
+#[test]
+fn default_bounds_only_where_needed() {
+    // Vec<T> is always Default, so T is not bounded
+    let s: Stack<NotDefault> = Stack::new();
+    assert!(s.items.is_empty());
+
+    // A bare T is defaulted, so new requires T: Default
+    let s: Slot<u8> = Slot::new(None);
+    assert_eq!(0, s.value);
+    assert_eq!(None, s.fallback);
+}
@@ -0,0 +1,974 @@
+// The getters of std types stabilized after the minimum supported Rust version are tested too
+#![allow(clippy::incompatible_msrv)]
+
+use unprolix::Getters;
+
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+#[derive(Getters)]
+struct Canvas {
+    #[unprolix(dyn)]
+    shape: Box<dyn Shape>,
+}
+
+#[test]
+fn dyn_getter() {
+    let c = Canvas {
+        shape: Box::new(Square(3)),
+    };
+
+    let shape: &dyn Shape = c.shape();
+    assert_eq!(9, shape.area());
+}
+
+#[derive(Getters)]
+struct Qualified {
+    #[unprolix(unprolix::copy)]
+    c: u8,
+}
+
+#[test]
+fn qualified_keys() {
+    assert_eq!(3, Qualified { c: 3 }.c());
+}
+
+#[derive(Getters)]
+struct Cells {
+    #[unprolix(cell_get)]
+    counter: std::cell::Cell<u32>,
+    #[unprolix(cell_get)]
+    names: std::cell::RefCell<Vec<String>>,
+}
+
+#[test]
+fn cell_getters() {
+    let c = Cells {
+        counter: std::cell::Cell::new(1),
+        names: std::cell::RefCell::new(vec![]),
+    };
+
+    c.counter.set(2);
+    assert_eq!(2, c.counter());
+
+    c.names.borrow_mut().push(String::from("a"));
+    assert_eq!(vec![String::from("a")], *c.names());
+}
+
+#[derive(Getters)]
+struct Config {
+    #[unprolix(as_path)]
+    root: std::path::PathBuf,
+}
+
+#[test]
+fn as_path() {
+    let c = Config {
+        root: std::path::PathBuf::from("/tmp"),
+    };
+
+    let root: &std::path::Path = c.root();
+    assert_eq!(std::path::Path::new("/tmp"), root);
+}
+
+#[derive(Getters)]
+#[unprolix(trait = "PointAccessors")]
+struct Point {
+    #[unprolix(copy)]
+    x: i32,
+    label: String,
+}
+
+fn describe(p: &impl PointAccessors) -> String {
+    format!("{}={}", p.label(), p.x())
+}
+
+#[test]
+fn trait_accessors() {
+    let p = Point {
+        x: 4,
+        label: String::from("x"),
+    };
+
+    assert_eq!("x=4", describe(&p));
+
+    let p: &dyn PointAccessors = &p;
+    assert_eq!(4, p.x());
+}
+
+#[derive(Getters)]
+struct Shared {
+    #[unprolix(deref_inner)]
+    owned: Box<[u8; 2]>,
+    #[unprolix(deref_inner)]
+    shared: std::sync::Arc<Vec<u8>>,
+}
+
+#[test]
+fn deref_inner() {
+    let s = Shared {
+        owned: Box::new([1, 2]),
+        shared: std::sync::Arc::new(vec![1]),
+    };
+
+    let owned: &[u8; 2] = s.owned();
+    let shared: &Vec<u8> = s.shared();
+
+    assert_eq!(&[1, 2], owned);
+    assert_eq!(&vec![1], shared);
+}
+
+#[derive(Getters)]
+struct Names {
+    #[unprolix(as_str)]
+    first: String,
+    #[unprolix(as_str)]
+    last: Box<str>,
+}
+
+#[test]
+fn as_str() {
+    let n = Names {
+        first: String::from("Ada"),
+        last: Box::from("Lovelace"),
+    };
+
+    let first: &str = n.first();
+    let last: &str = n.last();
+
+    assert_eq!("Ada", first);
+    assert_eq!("Lovelace", last);
+}
+
+trait Storage {
+    type Item;
+}
+
+struct Bytes;
+
+impl Storage for Bytes {
+    type Item = Vec<u8>;
+}
+
+#[derive(Getters)]
+struct Holder<S: Storage> {
+    item: <S as Storage>::Item,
+    other: S::Item,
+}
+
+#[test]
+fn associated_type() {
+    let h: Holder<Bytes> = Holder {
+        item: vec![1],
+        other: vec![2],
+    };
+
+    assert_eq!(&vec![1], h.item());
+    assert_eq!(&vec![2], h.other());
+}
+
+#[derive(Getters)]
+#[unprolix(rename_all = "camelCase")]
+struct Camel {
+    max_width: u32,
+    #[unprolix(rename = "height")]
+    max_height: u32,
+}
+
+#[test]
+fn rename_all_camel_case() {
+    let c = Camel {
+        max_width: 1,
+        max_height: 2,
+    };
+
+    assert_eq!(1, *c.maxWidth());
+    assert_eq!(2, *c.height());
+}
+
+#[derive(Getters)]
+#[unprolix(rename_all = "PascalCase")]
+struct Pascal {
+    max_width: u32,
+}
+
+#[test]
+fn rename_all_pascal_case() {
+    let p = Pascal { max_width: 1 };
+
+    assert_eq!(1, *p.MaxWidth());
+}
+
+#[derive(Getters)]
+struct Frame {
+    #[unprolix(returns = "dyn Shape")]
+    square: Square,
+}
+
+#[test]
+fn returns_trait_object() {
+    let f = Frame { square: Square(3) };
+    let shape: &dyn Shape = f.square();
+
+    assert_eq!(9, shape.area());
+}
+
+#[derive(Getters)]
+#[unprolix(getter_kind = "ref")]
+struct RefKind {
+    a: u8,
+    #[unprolix(copy)]
+    b: u8,
+}
+
+#[test]
+fn getter_kind_ref() {
+    let r = RefKind { a: 1, b: 2 };
+
+    assert_eq!(&1, r.a());
+    assert_eq!(2, r.b());
+}
+
+#[derive(Getters)]
+#[unprolix(getter_kind = "clone")]
+struct CloneKind {
+    name: String,
+    #[unprolix(ref)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn getter_kind_clone() {
+    let c = CloneKind {
+        name: String::from("a"),
+        tags: vec![],
+    };
+    let name: String = c.name();
+    let tags: &Vec<String> = c.tags();
+
+    assert_eq!("a", name);
+    assert!(tags.is_empty());
+}
+
+#[derive(Getters)]
+#[unprolix(getter_kind = "copy")]
+struct CopyKind {
+    x: u32,
+    y: u32,
+    #[unprolix(as_slice)]
+    points: Vec<u32>,
+}
+
+#[test]
+fn getter_kind_copy() {
+    let c = CopyKind {
+        x: 1,
+        y: 2,
+        points: vec![3],
+    };
+    let sum: u32 = c.x() + c.y();
+
+    assert_eq!(3, sum);
+    assert_eq!(&[3], c.points());
+}
+
+#[derive(Getters)]
+struct Rgb(#[unprolix(copy)] u8, #[unprolix(rename = "green")] u8, u8);
+
+#[test]
+fn tuple_struct() {
+    let c = Rgb(1, 2, 3);
+
+    assert_eq!(1, c.field_0());
+    assert_eq!(&2, c.green());
+    assert_eq!(&3, c.field_2());
+}
+
+#[derive(Getters)]
+struct Samples(#[unprolix(as_slice)] Vec<u8>, #[unprolix(skip)] u8);
+
+impl Samples {
+    // Would clash with the generated method if it was emitted
+    fn field_1(&self) -> u8 {
+        self.1
+    }
+}
+
+#[test]
+fn tuple_struct_slice_and_skip() {
+    let s = Samples(vec![1, 2], 3);
+
+    assert_eq!(&[1, 2], s.field_0());
+    assert_eq!(3, s.field_1());
+}
+
+#[derive(Getters)]
+struct Observer {
+    #[unprolix(upgrade)]
+    subject: std::sync::Weak<String>,
+    #[unprolix(upgrade)]
+    parent: std::rc::Weak<u8>,
+}
+
+#[test]
+fn weak_upgrade() {
+    let subject = std::sync::Arc::new(String::from("subject"));
+    let parent = std::rc::Rc::new(1);
+    let o = Observer {
+        subject: std::sync::Arc::downgrade(&subject),
+        parent: std::rc::Rc::downgrade(&parent),
+    };
+
+    assert_eq!(Some(subject.clone()), o.subject());
+    assert_eq!(Some(parent.clone()), o.parent());
+
+    drop(subject);
+    drop(parent);
+
+    assert_eq!(None, o.subject());
+    assert_eq!(None, o.parent());
+}
+
+#[derive(Getters)]
+#[unprolix(copy)]
+struct Particle {
+    mass: f64,
+    charged: bool,
+    #[unprolix(ref)]
+    id: u64,
+    label: String,
+    #[unprolix(copy)]
+    position: (f64, f64),
+}
+
+#[test]
+fn container_copy_with_ref_override() {
+    let p = Particle {
+        mass: 1.5,
+        charged: true,
+        id: 7,
+        label: String::from("e"),
+        position: (1.0, 2.0),
+    };
+    let mass: f64 = p.mass();
+    let charged: bool = p.charged();
+    let id: &u64 = p.id();
+    let label: &String = p.label();
+    let position: (f64, f64) = p.position();
+
+    assert_eq!(1.5, mass);
+    assert!(charged);
+    assert_eq!(&7, id);
+    assert_eq!("e", label);
+    assert_eq!((1.0, 2.0), position);
+}
+
+#[derive(Getters)]
+#[unprolix(arrays_as_slices)]
+struct Packet {
+    header: [u8; 4],
+    payload: [u16; 3],
+    #[unprolix(copy)]
+    checksum: [u8; 2],
+    len: usize,
+}
+
+#[test]
+fn arrays_as_slices() {
+    let p = Packet {
+        header: [1, 2, 3, 4],
+        payload: [5, 6, 7],
+        checksum: [8, 9],
+        len: 3,
+    };
+    let header: &[u8] = p.header();
+    let payload: &[u16] = p.payload();
+    let checksum: [u8; 2] = p.checksum();
+
+    assert_eq!(&[1, 2, 3, 4], header);
+    assert_eq!(&[5, 6, 7], payload);
+    assert_eq!([8, 9], checksum);
+    assert_eq!(&3, p.len());
+}
+
+#[allow(clippy::box_collection)]
+#[derive(Getters)]
+struct Boxed {
+    #[unprolix(deref_as = "Vec<u8>")]
+    bytes: Box<Vec<u8>>,
+    #[unprolix(deref_as = "[u8]")]
+    raw: Box<Vec<u8>>,
+}
+
+#[test]
+fn deref_as() {
+    let b = Boxed {
+        bytes: Box::new(vec![1, 2]),
+        raw: Box::new(vec![3]),
+    };
+    let bytes: &Vec<u8> = b.bytes();
+    let raw: &[u8] = b.raw();
+
+    assert_eq!(&vec![1, 2], bytes);
+    assert_eq!(&[3], raw);
+}
+
+#[derive(Getters)]
+struct Loaded {
+    #[unprolix(ok_ref)]
+    contents: Result<String, std::io::Error>,
+}
+
+#[test]
+fn ok_ref() {
+    let ok = Loaded {
+        contents: Ok(String::from("data")),
+    };
+    let err = Loaded {
+        contents: Err(std::io::Error::other("missing")),
+    };
+
+    let contents: Result<&String, &std::io::Error> = ok.contents();
+
+    assert_eq!("data", contents.unwrap());
+    assert_eq!("missing", err.contents().unwrap_err().to_string());
+}
+
+#[derive(Getters)]
+struct Lazy {
+    #[unprolix(once_get)]
+    config: std::cell::OnceCell<String>,
+    #[unprolix(once_get)]
+    shared: std::sync::OnceLock<u32>,
+}
+
+#[test]
+fn once_get() {
+    let l = Lazy {
+        config: std::cell::OnceCell::new(),
+        shared: std::sync::OnceLock::new(),
+    };
+
+    assert_eq!(None, l.config());
+    assert_eq!(None, l.shared());
+
+    l.config.set(String::from("loaded")).unwrap();
+    l.shared.set(7).unwrap();
+
+    assert_eq!(Some(&String::from("loaded")), l.config());
+    assert_eq!(Some(&7), l.shared());
+}
+
+#[derive(Getters)]
+struct Scores {
+    #[unprolix(lookup)]
+    by_name: std::collections::HashMap<String, i32>,
+    #[unprolix(lookup)]
+    banned: std::collections::HashSet<String>,
+}
+
+#[test]
+fn lookup() {
+    let s = Scores {
+        by_name: vec![(String::from("ana"), 10)].into_iter().collect(),
+        banned: vec![String::from("bob")].into_iter().collect(),
+    };
+
+    assert_eq!(Some(&10), s.by_name_get(&String::from("ana")));
+    assert_eq!(None, s.by_name_get(&String::from("bob")));
+    assert!(s.by_name_contains(&String::from("ana")));
+    assert!(!s.by_name_contains(&String::from("bob")));
+    assert_eq!(1, s.by_name().len());
+
+    assert!(s.banned_contains(&String::from("bob")));
+    assert_eq!(
+        Some(&String::from("bob")),
+        s.banned_get(&String::from("bob"))
+    );
+}
+
+#[derive(Getters)]
+struct Dimensions {
+    #[unprolix(copy, with_ref)]
+    width: u32,
+}
+
+#[test]
+fn with_ref() {
+    let d = Dimensions { width: 3 };
+    let value: u32 = d.width();
+    let reference: &u32 = d.width_ref();
+
+    assert_eq!(3, value);
+    assert_eq!(&3, reference);
+}
+
+#[derive(Getters)]
+struct Registry {
+    #[unprolix(lock)]
+    hits: std::sync::Mutex<u32>,
+    #[unprolix(lock)]
+    names: std::sync::RwLock<Vec<String>>,
+}
+
+#[test]
+fn lock() {
+    let s = Registry {
+        hits: std::sync::Mutex::new(1),
+        names: std::sync::RwLock::new(vec![String::from("a")]),
+    };
+
+    *s.hits() += 1;
+
+    assert_eq!(2, *s.hits());
+    assert_eq!(2, *s.try_hits().unwrap());
+    assert_eq!(&[String::from("a")], s.names().as_slice());
+    assert_eq!(1, s.try_names().unwrap().len());
+}
+
+#[derive(Getters)]
+struct Retry {
+    #[unprolix(opt_slice)]
+    delays: Option<Vec<u64>>,
+}
+
+#[test]
+fn opt_slice() {
+    let some = Retry {
+        delays: Some(vec![10, 20]),
+    };
+    let none = Retry { delays: None };
+
+    assert_eq!(Some(&[10, 20][..]), some.delays());
+    assert_eq!(None, none.delays());
+}
+
+// The doc alias of a renamed getter must not be repeated on the trait implementation
+#[derive(Getters)]
+#[unprolix(trait = "TimeoutAccessors")]
+struct Timeout {
+    #[unprolix(rename = "millis", copy)]
+    timeout_ms: u64,
+}
+
+#[test]
+fn rename_trait_alias() {
+    assert_eq!(250, Timeout { timeout_ms: 250 }.millis());
+}
+
+#[derive(Getters)]
+#[unprolix(only_type = "String")]
+struct Contact {
+    name: String,
+    email: String,
+    age: u8,
+}
+
+impl Contact {
+    // This would clash with the generated method if it was emitted
+    fn age(&self) -> u8 {
+        self.age
+    }
+}
+
+#[test]
+fn only_type() {
+    let c = Contact {
+        name: String::from("Ana"),
+        email: String::from("ana@example.com"),
+        age: 30,
+    };
+
+    assert_eq!("Ana", c.name());
+    assert_eq!("ana@example.com", c.email());
+    assert_eq!(30, c.age());
+}
+
+#[derive(Getters)]
+#[unprolix(copy, arrays_as_slices)]
+struct AudioChunk {
+    samples: [i16; 2],
+    #[unprolix(raw)]
+    header: [u8; 2],
+    #[unprolix(raw)]
+    index: u32,
+}
+
+#[test]
+fn raw() {
+    let f = AudioChunk {
+        samples: [1, -1],
+        header: [0x52, 0x49],
+        index: 4,
+    };
+    let samples: &[i16] = f.samples();
+    let header: &[u8; 2] = f.header();
+    let index: &u32 = f.index();
+
+    assert_eq!(&[1, -1], samples);
+    assert_eq!(&[0x52, 0x49], header);
+    assert_eq!(&4, index);
+}
+
+#[derive(Getters)]
+#[repr(transparent)]
+#[unprolix(transparent_ref)]
+struct UserId(u64);
+
+#[test]
+fn transparent_ref() {
+    let id = UserId(7);
+
+    assert_eq!(&7, id.as_inner());
+    assert_eq!(&7, id.field_0());
+}
+
+#[derive(Getters)]
+struct Greeting {
+    #[unprolix(as_str)]
+    text: std::borrow::Cow<'static, str>,
+}
+
+#[test]
+fn as_str_cow() {
+    let borrowed = Greeting {
+        text: std::borrow::Cow::Borrowed("hi"),
+    };
+    let owned = Greeting {
+        text: std::borrow::Cow::Owned(String::from("hello")),
+    };
+
+    assert_eq!("hi", borrowed.text());
+    assert_eq!("hello", owned.text());
+}
+
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Getters)]
+struct Player {
+    #[unprolix(skip)]
+    name: String,
+    #[unprolix(flatten(x = "i32", y = "i32"))]
+    position: Position,
+}
+
+#[test]
+fn flatten() {
+    let p = Player {
+        name: String::from("p1"),
+        position: Position { x: 3, y: -2 },
+    };
+
+    assert_eq!("p1", p.name);
+    assert_eq!(&3, p.x());
+    assert_eq!(&-2, p.y());
+    assert_eq!(3, p.position().x);
+}
+
+#[derive(Getters)]
+struct Inventory {
+    #[unprolix(get_primitive)]
+    capacity: std::num::NonZeroU32,
+    #[unprolix(get_primitive)]
+    reserved: std::num::NonZero<u8>,
+}
+
+#[test]
+fn get_primitive() {
+    let i = Inventory {
+        capacity: std::num::NonZeroU32::new(12).unwrap(),
+        reserved: std::num::NonZero::new(2).unwrap(),
+    };
+    let capacity: u32 = i.capacity();
+    let reserved: u8 = i.reserved();
+
+    assert_eq!(12, capacity);
+    assert_eq!(2, reserved);
+}
+
+struct SmallVec<A>(A);
+
+impl<T, const N: usize> SmallVec<[T; N]> {
+    fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+}
+
+struct ArrayVec<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> ArrayVec<T, N> {
+    fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+}
+
+struct ByteBuf(Vec<u8>);
+
+impl ByteBuf {
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(Getters)]
+struct Buffers {
+    #[unprolix(as_slice)]
+    inline: SmallVec<[u16; 2]>,
+    #[unprolix(as_slice)]
+    fixed: ArrayVec<char, 3>,
+    #[unprolix(as_slice, elem = "u8")]
+    raw_bytes: ByteBuf,
+}
+
+#[test]
+fn as_slice_vec_like() {
+    let b = Buffers {
+        inline: SmallVec([1, 2]),
+        fixed: ArrayVec(['a', 'b', 'c']),
+        raw_bytes: ByteBuf(vec![7]),
+    };
+    let inline: &[u16] = b.inline();
+    let fixed: &[char] = b.fixed();
+    let raw_bytes: &[u8] = b.raw_bytes();
+
+    assert_eq!(&[1, 2], inline);
+    assert_eq!(&['a', 'b', 'c'], fixed);
+    assert_eq!(&[7], raw_bytes);
+}
+
+#[derive(Getters)]
+struct Tally {
+    #[unprolix(iter_mut)]
+    points: Vec<u32>,
+    #[unprolix(iter_mut)]
+    by_name: std::collections::HashMap<String, u32>,
+}
+
+#[test]
+fn iter_mut() {
+    let mut s = Tally {
+        points: vec![1, 2],
+        by_name: vec![(String::from("a"), 10)].into_iter().collect(),
+    };
+
+    s.points_iter_mut().for_each(|p| *p *= 10);
+    s.by_name_iter_mut().for_each(|p| *p += 1);
+
+    assert_eq!(&vec![10, 20], s.points());
+    assert_eq!(Some(&11), s.by_name().get("a"));
+}
+
+type Blob = Vec<u8>;
+type Text = String;
+
+#[derive(Getters)]
+struct Attachment {
+    #[unprolix(as_slice, ty = "Vec<u8>")]
+    data: Blob,
+    #[unprolix(as_str, ty = "String")]
+    mime: Text,
+    #[unprolix(ty = "Vec<u8>")]
+    thumbnail: Blob,
+}
+
+#[test]
+fn type_alias() {
+    let a = Attachment {
+        data: vec![1, 2],
+        mime: String::from("image/png"),
+        thumbnail: vec![3],
+    };
+    let data: &[u8] = a.data();
+    let mime: &str = a.mime();
+    let thumbnail: &Blob = a.thumbnail();
+
+    assert_eq!(&[1, 2], data);
+    assert_eq!("image/png", mime);
+    assert_eq!(&vec![3], thumbnail);
+}
+
+#[derive(Getters)]
+#[unprolix(inline)]
+struct Extent {
+    width: u32,
+    labels: Vec<String>,
+}
+
+#[derive(Getters)]
+#[unprolix(inline, trait = "MarginAccessors")]
+struct Margin {
+    top: u32,
+}
+
+#[test]
+fn inline() {
+    let extent = Extent {
+        width: 4,
+        labels: vec![String::from("a")],
+    };
+    let margin = Margin { top: 2 };
+
+    assert_eq!(&4, extent.width());
+    assert_eq!(&vec![String::from("a")], extent.labels());
+    assert_eq!(&2, margin.top());
+}
+
+#[derive(Getters)]
+struct Meters<U>(#[unprolix(primary)] f64, std::marker::PhantomData<U>);
+
+#[test]
+fn primary() {
+    let m: Meters<()> = Meters(2.5, std::marker::PhantomData);
+
+    assert_eq!(&2.5, m.field_0());
+    assert_eq!(&2.5, m.value());
+    assert_eq!(&std::marker::PhantomData, m.field_1());
+}
+
+// `must_use` is declared by the trait only, so the implementation doesn't trigger
+// `unused_attributes`
+#[deny(unused_attributes)]
+mod guarded {
+    use std::cell::RefCell;
+    use unprolix::Getters;
+
+    #[derive(Getters)]
+    #[unprolix(trait = "LedgerAccessors")]
+    pub struct Ledger {
+        #[unprolix(cell_get)]
+        entries: RefCell<Vec<i64>>,
+    }
+
+    pub fn ledger(entries: Vec<i64>) -> Ledger {
+        Ledger {
+            entries: RefCell::new(entries),
+        }
+    }
+}
+
+#[test]
+fn must_use_guard() {
+    use guarded::LedgerAccessors;
+
+    let ledger = guarded::ledger(vec![3, -1]);
+    let entries = ledger.entries();
+
+    assert_eq!(&[3, -1], entries.as_slice());
+}
+
+#[derive(Getters)]
+#[unprolix(document_skipped)]
+struct Credentials {
+    user: String,
+    #[unprolix(skip = "never exposed in logs")]
+    password: String,
+    #[unprolix(skip)]
+    attempts: u8,
+}
+
+#[test]
+fn document_skipped() {
+    let c = Credentials {
+        user: String::from("ana"),
+        password: String::from("secret"),
+        attempts: 0,
+    };
+
+    assert_eq!("ana", c.user());
+    assert_eq!(("secret", 0), (c.password.as_str(), c.attempts));
+}
+
+#[derive(Getters)]
+struct Parser<'a> {
+    input: &'a str,
+    #[unprolix(ref)]
+    rest: &'a [u8],
+}
+
+fn first_word(input: &str) -> &str {
+    // The getter borrows from the input rather than from the parser, which is dropped here
+    let parser = Parser {
+        input,
+        rest: input.as_bytes(),
+    };
+
+    assert_eq!(&input.as_bytes(), parser.rest());
+
+    parser.input().split(' ').next().unwrap()
+}
+
+#[test]
+fn reference_lifetime() {
+    let input = String::from("hello world");
+
+    assert_eq!("hello", first_word(&input));
+}
+
+#[derive(Getters)]
+#[unprolix(copy)]
+enum Handle {
+    Open {
+        path: String,
+        fd: i32,
+        #[unprolix(skip)]
+        #[allow(dead_code)]
+        flags: u32,
+    },
+}
+
+#[derive(Getters)]
+enum Wrapped<T> {
+    Value(T, #[unprolix(clone)] String),
+}
+
+#[test]
+fn single_variant_enum() {
+    let h = Handle::Open {
+        path: String::from("/tmp/a"),
+        fd: 3,
+        flags: 0,
+    };
+    let w = Wrapped::Value(7_u8, String::from("seven"));
+
+    assert_eq!("/tmp/a", h.path());
+    assert_eq!(3, h.fd());
+    assert_eq!(&7, w.field_0());
+    assert_eq!(String::from("seven"), w.field_1());
+}
+
+#[derive(Getters)]
+struct Catalog {
+    #[unprolix(project = "as_deref().unwrap_or_default()", returns = "&[u32]")]
+    ids: Option<Box<[u32]>>,
+    #[unprolix(project = "borrow().len()", returns = "usize")]
+    pending: std::cell::RefCell<Vec<u32>>,
+}
+
+#[test]
+fn project() {
+    let c = Catalog {
+        ids: Some(vec![4, 2].into_boxed_slice()),
+        pending: std::cell::RefCell::new(vec![1]),
+    };
+    let empty = Catalog {
+        ids: None,
+        pending: std::cell::RefCell::new(vec![]),
+    };
+
+    assert_eq!(&[4, 2], c.ids());
+    assert_eq!(1, c.pending());
+    assert!(empty.ids().is_empty());
+    assert_eq!(0, empty.pending());
+}
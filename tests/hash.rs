@@ -0,0 +1,48 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use unprolix::Hashable;
+
+#[derive(Hashable)]
+struct Entry {
+    key: String,
+    version: u32,
+    #[unprolix(skip)]
+    hits: u64,
+}
+
+#[derive(Hashable)]
+struct Pair(u8, #[unprolix(skip)] u8);
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut state = DefaultHasher::new();
+    t.hash(&mut state);
+    state.finish()
+}
+
+#[test]
+fn skipped_attributes_ignored() {
+    let a = Entry {
+        key: String::from("k"),
+        version: 1,
+        hits: 0,
+    };
+    let b = Entry {
+        key: String::from("k"),
+        version: 1,
+        hits: 42,
+    };
+    let c = Entry {
+        key: String::from("k"),
+        version: 2,
+        hits: 0,
+    };
+
+    assert_ne!(a.hits, b.hits);
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(hash(&a), hash(&c));
+
+    let (p, q) = (Pair(1, 2), Pair(1, 3));
+
+    assert_ne!(p.1, q.1);
+    assert_eq!(hash(&p), hash(&q));
+}
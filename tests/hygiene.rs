@@ -0,0 +1,114 @@
+use unprolix::{Builder, Constructor, FromTuple, Getters, IntoTuple, Reset, Setters};
+
+#[test]
+fn derive_inside_function() {
+    // Shadow the prelude names, so the generated code must use absolute paths
+    #[allow(dead_code)]
+    struct Default;
+    #[allow(dead_code)]
+    struct Option;
+    #[allow(dead_code)]
+    struct Result;
+    #[allow(dead_code)]
+    struct From;
+
+    #[derive(Debug, PartialEq, Builder, Constructor, Getters, Reset, Setters)]
+    #[unprolix(derive_default)]
+    struct Local {
+        a: u8,
+        #[unprolix(default, overridable)]
+        b: u8,
+        c: Vec<u8>,
+    }
+
+    #[derive(Getters)]
+    struct Loaded {
+        #[unprolix(ok_ref)]
+        value: std::result::Result<u8, ()>,
+        #[unprolix(upgrade)]
+        parent: std::sync::Weak<u8>,
+    }
+
+    #[derive(FromTuple, IntoTuple)]
+    struct Pair(u8, #[unprolix(default)] u8);
+
+    let mut l = Local::new(1, None, vec![2]);
+
+    assert_eq!(&1, l.a());
+    assert_eq!(&vec![2], l.c());
+
+    l.set_a(3);
+    l.reset();
+
+    assert_eq!(
+        <Local as std::default::Default>::default(),
+        Local::builder().a(0).c(vec![]).build().unwrap()
+    );
+
+    let loaded = Loaded {
+        value: Ok(1),
+        parent: std::sync::Weak::new(),
+    };
+
+    assert_eq!(Ok(&1), loaded.value());
+    assert_eq!(None, loaded.parent());
+
+    let p = Pair::from((1,));
+    let (a, b): (u8, u8) = p.into();
+
+    assert_eq!((1, 0), (a, b));
+}
+
+mod shadowed {
+    use unprolix::Getters;
+
+    #[allow(dead_code)]
+    pub struct Vec;
+    #[allow(dead_code)]
+    pub struct String;
+    #[allow(dead_code)]
+    pub struct Option;
+    #[allow(dead_code)]
+    pub struct Default;
+
+    // Taking the attributes by value, these methods are selected by a method call before the
+    // inherent ones, so only the qualified calls compile
+    #[allow(dead_code, clippy::wrong_self_convention)]
+    pub trait Shadowing: Sized {
+        fn as_slice(self) {}
+        fn as_str(self) {}
+        fn as_path(self) {}
+    }
+
+    impl Shadowing for std::vec::Vec<u8> {}
+    impl Shadowing for std::string::String {}
+    impl Shadowing for std::path::PathBuf {}
+
+    #[derive(Getters)]
+    #[unprolix(qualified)]
+    pub struct Document {
+        #[unprolix(as_slice)]
+        lines: std::vec::Vec<u8>,
+        #[unprolix(as_str)]
+        title: std::string::String,
+        #[unprolix(as_path)]
+        path: std::path::PathBuf,
+    }
+
+    pub fn document() -> Document {
+        Document {
+            lines: vec![1, 2],
+            title: "title".into(),
+            path: "a/b".into(),
+        }
+    }
+}
+
+#[test]
+fn qualified_paths() {
+    let d = shadowed::document();
+
+    assert_eq!(&[1, 2], d.lines());
+    assert_eq!("title", d.title());
+    assert_eq!(std::path::Path::new("a/b"), d.path());
+}
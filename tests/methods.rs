@@ -0,0 +1,67 @@
+use unprolix::{unprolix_methods, UnprolixMethods};
+
+#[derive(UnprolixMethods)]
+struct Wallet {
+    #[unprolix(get)]
+    owner: String,
+    coins: u64,
+}
+
+#[unprolix_methods(for = Wallet)]
+impl Wallet {
+    pub fn new(owner: String) -> Self {
+        Self { owner, coins: 0 }
+    }
+
+    pub fn deposit(&mut self, coins: u64) {
+        let total = self.coins() + coins;
+
+        self.set_coins(total);
+    }
+}
+
+#[test]
+fn inject_into_impl() {
+    let mut w = Wallet::new(String::from("alice"));
+
+    w.deposit(3);
+    w.deposit(4);
+
+    assert_eq!("alice", w.owner());
+    assert_eq!(&7, w.coins());
+}
+
+#[derive(UnprolixMethods)]
+struct Vault<T> {
+    items: Vec<T>,
+    label: &'static str,
+}
+
+// The fields of the struct are visible from child modules, where it is named with its path
+mod vault {
+    use unprolix::unprolix_methods;
+
+    #[unprolix_methods(for = super::Vault<T>)]
+    impl<T> super::Vault<T> {
+        pub fn new(label: &'static str) -> Self {
+            Self {
+                items: vec![],
+                label,
+            }
+        }
+
+        pub fn describe(&self) -> String {
+            format!("{} with {} items", self.label(), self.items().len())
+        }
+    }
+}
+
+#[test]
+fn inject_by_path() {
+    let mut v = Vault::new("safe");
+
+    v.items_as_mut().push(1);
+    v.set_label("box");
+
+    assert_eq!("box with 1 items", v.describe());
+}
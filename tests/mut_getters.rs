@@ -0,0 +1,63 @@
+use unprolix::MutGetters;
+
+#[derive(MutGetters)]
+struct Buffer {
+    data: Vec<u8>,
+    len: usize,
+    #[unprolix(skip)]
+    cap: usize,
+    pub id: u8,
+}
+
+impl Buffer {
+    // These would clash with the generated methods if they were emitted
+    fn data(&self) -> usize {
+        self.cap
+    }
+
+    fn set_len(&self) -> u8 {
+        self.id
+    }
+
+    fn cap_mut(&self) -> usize {
+        self.len
+    }
+
+    fn id_mut(&self) -> u8 {
+        self.id
+    }
+}
+
+#[test]
+fn mut_getters() {
+    let mut b = Buffer {
+        data: vec![],
+        len: 0,
+        cap: 4,
+        id: 1,
+    };
+
+    b.data_mut().push(3);
+    *b.len_mut() += 1;
+
+    assert_eq!(vec![3], b.data);
+    assert_eq!(1, b.len);
+    assert_eq!(4, b.data());
+    assert_eq!(1, b.set_len());
+    assert_eq!(1, b.cap_mut());
+    assert_eq!(1, b.id_mut());
+}
+
+#[derive(MutGetters)]
+struct Pair(u8, #[unprolix(rename = "right")] String);
+
+#[test]
+fn tuple_struct() {
+    let mut p = Pair(1, String::new());
+
+    *p.field_0_mut() += 1;
+    p.right_mut().push('a');
+
+    assert_eq!(2, p.0);
+    assert_eq!("a", p.1);
+}
@@ -0,0 +1,30 @@
+use unprolix::PartialGetters;
+
+#[derive(PartialGetters)]
+struct Account {
+    #[unprolix(get)]
+    id: u64,
+    #[unprolix(get, as_str)]
+    owner: String,
+    secret: String,
+}
+
+impl Account {
+    // This would clash with a generated getter if it was emitted
+    fn secret(&self) -> usize {
+        self.secret.len()
+    }
+}
+
+#[test]
+fn only_annotated() {
+    let a = Account {
+        id: 1,
+        owner: String::from("ada"),
+        secret: String::from("hunter2"),
+    };
+
+    assert_eq!(&1, a.id());
+    assert_eq!("ada", a.owner());
+    assert_eq!(7, a.secret());
+}
@@ -0,0 +1,65 @@
+use unprolix::Reset;
+
+#[derive(Debug, PartialEq, Reset)]
+struct Pooled {
+    a: u8,
+    b: Vec<u8>,
+    #[unprolix(default = "String::from(\"idle\")")]
+    c: String,
+    #[unprolix(skip)]
+    d: u8,
+}
+
+#[test]
+fn reset_fields() {
+    let mut p = Pooled {
+        a: 3,
+        b: vec![1, 2],
+        c: String::from("busy"),
+        d: 7,
+    };
+
+    p.reset();
+
+    assert_eq!(
+        Pooled {
+            a: 0,
+            b: vec![],
+            c: String::from("idle"),
+            d: 7,
+        },
+        p
+    );
+}
+
+#[derive(Debug, PartialEq, Reset)]
+struct Keys {
+    #[unprolix(default = "1", skip)]
+    a: u8,
+    #[unprolix(copy, default = "2")]
+    b: u8,
+    #[unprolix(copy)]
+    #[unprolix(default = "3")]
+    c: u8,
+}
+
+#[test]
+fn several_keys() {
+    let mut k = Keys { a: 0, b: 0, c: 0 };
+
+    k.reset();
+
+    assert_eq!(Keys { a: 0, b: 2, c: 3 }, k);
+}
+
+#[derive(Debug, PartialEq, Reset)]
+struct Counter(u32, #[unprolix(default = "10")] u32, #[unprolix(skip)] u8);
+
+#[test]
+fn tuple_struct() {
+    let mut c = Counter(5, 6, 7);
+
+    c.reset();
+
+    assert_eq!(Counter(0, 10, 7), c);
+}
@@ -0,0 +1,369 @@
+use unprolix::Setters;
+
+#[derive(Setters)]
+#[unprolix(track_caller)]
+struct Tracked {
+    a: u8,
+}
+
+#[test]
+fn track_caller_setters() {
+    let mut t = Tracked { a: 0 };
+
+    t.set_a(1);
+    *t.a_as_mut() += 1;
+
+    assert_eq!(2, t.a);
+}
+
+#[derive(Setters)]
+struct Inventory {
+    #[unprolix(modify)]
+    items: Vec<String>,
+}
+
+#[test]
+fn modify() {
+    let mut i = Inventory { items: vec![] };
+
+    i.modify_items(|items| items.push(String::from("apple")));
+
+    assert_eq!(vec![String::from("apple")], i.items);
+}
+
+#[derive(Setters)]
+#[unprolix(rename_all = "camelCase")]
+struct Camel {
+    max_width: u32,
+    #[unprolix(rename = "width")]
+    min_width: u32,
+}
+
+#[test]
+fn rename_all_camel_case() {
+    let mut c = Camel {
+        max_width: 0,
+        min_width: 0,
+    };
+
+    c.setMaxWidth(1);
+    *c.maxWidthAsMut() += 1;
+    c.set_width(3);
+
+    assert_eq!(2, c.max_width);
+    assert_eq!(3, c.min_width);
+}
+
+#[derive(Setters)]
+struct Scratch {
+    #[unprolix(swap)]
+    buffer: Vec<u8>,
+    id: u8,
+}
+
+#[test]
+fn swap() {
+    let mut a = Scratch {
+        buffer: vec![1],
+        id: 1,
+    };
+    let mut b = Scratch {
+        buffer: vec![2, 3],
+        id: 2,
+    };
+
+    a.swap_buffer(&mut b);
+
+    assert_eq!(vec![2, 3], a.buffer);
+    assert_eq!(vec![1], b.buffer);
+    assert_eq!(1, a.id);
+    assert_eq!(2, b.id);
+}
+
+#[derive(Setters)]
+struct Point(i32, i32, #[unprolix(skip)] u8);
+
+impl Point {
+    // Would clash with the generated method if it was emitted
+    fn set_field_2(&mut self) {
+        self.2 += 1;
+    }
+}
+
+#[test]
+fn tuple_struct() {
+    let mut p = Point(0, 0, 0);
+
+    p.set_field_0(1);
+    *p.field_1_as_mut() = 2;
+    p.set_field_2();
+
+    assert_eq!(1, p.0);
+    assert_eq!(2, p.1);
+    assert_eq!(1, p.2);
+}
+
+#[derive(Setters)]
+struct Cache {
+    #[unprolix(get_or_insert)]
+    value: Option<String>,
+}
+
+#[test]
+fn get_or_insert() {
+    let mut c = Cache { value: None };
+
+    c.value_or_insert_with(|| String::from("first")).push('!');
+    let v = c.value_or_insert_with(|| String::from("second"));
+
+    assert_eq!("first!", v);
+}
+
+#[derive(Setters)]
+struct Versioned {
+    #[unprolix(replace_with)]
+    version: u32,
+}
+
+#[test]
+fn replace_with() {
+    let mut v = Versioned { version: 1 };
+
+    assert_eq!(1, v.take_and_replace_version(5));
+    assert_eq!(5, v.replace_version_with(|current| current + 1));
+    assert_eq!(6, v.version);
+}
+
+#[derive(Debug, PartialEq, Setters)]
+#[unprolix(patch)]
+struct Profile {
+    nickname: String,
+    age: u8,
+    #[unprolix(skip)]
+    id: u64,
+}
+
+#[test]
+fn patch() {
+    let mut p = Profile {
+        nickname: String::from("ana"),
+        age: 30,
+        id: 1,
+    };
+
+    p.apply_patch(ProfilePatch {
+        age: Some(31),
+        ..Default::default()
+    });
+
+    assert_eq!(
+        Profile {
+            nickname: String::from("ana"),
+            age: 31,
+            id: 1,
+        },
+        p
+    );
+
+    p.apply_patch(ProfilePatch {
+        nickname: Some(String::from("bia")),
+        age: None,
+    });
+
+    assert_eq!("bia", p.nickname);
+    assert_eq!(31, p.age);
+}
+
+#[derive(Setters)]
+#[unprolix(patch)]
+struct Tagged<'a, T, U> {
+    tag: &'a str,
+    value: T,
+    #[unprolix(skip)]
+    origin: Option<U>,
+}
+
+#[test]
+fn patch_unused_generics() {
+    let mut t = Tagged {
+        tag: "a",
+        value: 1u8,
+        origin: Some('x'),
+    };
+
+    t.apply_patch(TaggedPatch {
+        tag: Some("b"),
+        value: None,
+    });
+
+    assert_eq!("b", t.tag);
+    assert_eq!(1, t.value);
+    assert_eq!(Some('x'), t.origin);
+}
+
+#[derive(Setters)]
+struct Stats {
+    #[unprolix(atomic)]
+    requests: std::sync::atomic::AtomicUsize,
+    #[unprolix(atomic)]
+    healthy: std::sync::atomic::AtomicBool,
+}
+
+#[test]
+fn atomic() {
+    use std::sync::atomic::Ordering;
+
+    let s = Stats {
+        requests: std::sync::atomic::AtomicUsize::new(0),
+        healthy: std::sync::atomic::AtomicBool::new(false),
+    };
+
+    s.set_requests(5, Ordering::SeqCst);
+    s.set_healthy(true, Ordering::SeqCst);
+
+    assert_eq!(5, s.requests(Ordering::SeqCst));
+    assert!(s.healthy(Ordering::SeqCst));
+}
+
+#[derive(Setters)]
+struct Query {
+    #[unprolix(clearable)]
+    filters: Vec<String>,
+    #[unprolix(clearable, default = "25")]
+    limit: usize,
+}
+
+#[test]
+fn clearable() {
+    let mut q = Query {
+        filters: vec![],
+        limit: 25,
+    };
+
+    q.set_filters(vec![String::from("active")]);
+    q.set_limit(100);
+    q.clear_filters();
+
+    assert!(q.filters.is_empty());
+    assert_eq!(100, q.limit);
+
+    q.clear_limit();
+
+    assert_eq!(25, q.limit);
+}
+
+#[derive(Setters)]
+#[unprolix(reflect)]
+struct Sprite {
+    label: String,
+    scale_factor: f32,
+}
+
+#[test]
+fn reflect() {
+    let mut s = Sprite {
+        label: String::new(),
+        scale_factor: 1.0,
+    };
+
+    s.set(SpriteField::Label, Box::new(String::from("hero")))
+        .unwrap();
+    s.set(SpriteField::ScaleFactor, Box::new(2.5_f32)).unwrap();
+
+    assert_eq!("hero", s.label);
+    assert_eq!(2.5, s.scale_factor);
+
+    let rejected = s.set(SpriteField::ScaleFactor, Box::new(3_u8)).unwrap_err();
+
+    assert_eq!(Some(&3), rejected.downcast_ref::<u8>());
+    assert_eq!(2.5, s.scale_factor);
+}
+
+#[derive(Setters)]
+#[unprolix(reflect)]
+struct Tile<'a, T> {
+    name: &'a str,
+    value: T,
+}
+
+#[test]
+fn reflect_generic() {
+    let mut t = Tile {
+        name: "a",
+        value: 1_u8,
+    };
+
+    t.set(TileField::Name, Box::new("b")).unwrap();
+    t.set(TileField::Value, Box::new(2_u8)).unwrap();
+
+    assert_eq!(("b", 2), (t.name, t.value));
+    assert!(t.set(TileField::Value, Box::new(3_u16)).is_err());
+}
+
+#[derive(Setters)]
+#[unprolix(inline, track_caller)]
+struct Brush {
+    width: u8,
+}
+
+#[test]
+fn inline() {
+    let mut b = Brush { width: 1 };
+
+    b.set_width(3);
+
+    assert_eq!(3, b.width);
+}
+
+#[derive(Setters, Debug, PartialEq)]
+#[unprolix(copy_from)]
+struct Theme {
+    name: String,
+    colors: Vec<u32>,
+    #[unprolix(skip)]
+    revision: u8,
+}
+
+#[test]
+fn copy_from() {
+    let template = Theme {
+        name: String::from("dark"),
+        colors: vec![0x000000, 0x1e1e1e],
+        revision: 4,
+    };
+    let mut t = Theme {
+        name: String::from("light"),
+        colors: vec![],
+        revision: 1,
+    };
+
+    t.set_all(&template);
+
+    assert_eq!(
+        Theme {
+            name: String::from("dark"),
+            colors: vec![0x000000, 0x1e1e1e],
+            revision: 1,
+        },
+        t
+    );
+}
+
+#[derive(Setters)]
+struct Export {
+    #[unprolix(as_ref)]
+    destination: std::path::PathBuf,
+}
+
+#[test]
+fn as_ref_path() {
+    let mut e = Export {
+        destination: std::path::PathBuf::new(),
+    };
+
+    e.set_destination("/tmp/export");
+    assert_eq!(std::path::Path::new("/tmp/export"), e.destination);
+
+    e.set_destination(std::path::PathBuf::from("out"));
+    assert_eq!(std::path::Path::new("out"), e.destination);
+}
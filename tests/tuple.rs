@@ -0,0 +1,59 @@
+use unprolix::{FromTuple, IntoTuple};
+
+#[derive(IntoTuple)]
+struct Person {
+    name: String,
+    age: u8,
+    active: bool,
+}
+
+#[derive(IntoTuple)]
+struct Meters(f64);
+
+#[test]
+fn into_tuple() {
+    let p = Person {
+        name: String::from("Ana"),
+        age: 30,
+        active: true,
+    };
+    let (name, age, active): (String, u8, bool) = p.into();
+
+    assert_eq!("Ana", name);
+    assert_eq!(30, age);
+    assert!(active);
+
+    let (m,): (f64,) = Meters(1.5).into();
+
+    assert_eq!(1.5, m);
+}
+
+#[derive(Debug, PartialEq, FromTuple)]
+struct Rect {
+    width: u32,
+    height: u32,
+    #[unprolix(default = "width * height")]
+    area: u32,
+    #[unprolix(default)]
+    label: String,
+}
+
+#[derive(Debug, PartialEq, FromTuple)]
+struct Pair(u8, #[unprolix(skip)] u8, u16);
+
+#[test]
+fn from_tuple() {
+    assert_eq!(
+        Rect {
+            width: 2,
+            height: 3,
+            area: 6,
+            label: String::new(),
+        },
+        Rect::from((2, 3))
+    );
+
+    let p: Pair = (1, 2).into();
+
+    assert_eq!(Pair(1, 0, 2), p);
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "unsafe-uninit")]
+
+use std::mem::MaybeUninit;
+use unprolix::Constructor;
+
+#[derive(Constructor)]
+struct Scratch {
+    len: usize,
+    #[unprolix(uninit)]
+    buf: [MaybeUninit<u8>; 64],
+}
+
+#[test]
+fn new_uninit() {
+    let mut s = unsafe { Scratch::new_uninit(0) };
+
+    s.buf[0] = MaybeUninit::new(7);
+    s.len = 1;
+
+    assert_eq!(1, s.len);
+    assert_eq!(7, unsafe { s.buf[0].assume_init() });
+
+    let s = Scratch::new(2, [MaybeUninit::new(1); 64]);
+    assert_eq!(2, s.len);
+}